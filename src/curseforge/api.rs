@@ -1,12 +1,20 @@
+use std::collections::HashMap;
+#[cfg(feature = "async")]
+use std::future::Future;
+
 use super::error::CurseForgeResult;
+use super::schemas::BatchResponse;
 use super::schemas::Category;
 use super::schemas::DataResponse;
+use super::schemas::FeaturedModsResponse;
 use super::schemas::File;
 use super::schemas::Game;
 use super::schemas::Mod;
-use super::schemas::{GameVersionType, GameVersionsByType};
+use super::schemas::{FingerprintFuzzyMatchResult, FingerprintsMatchesResult, FolderFingerprint};
+use super::schemas::{GameVersionType, GameVersionsByType, GameVersionsByTypeV2};
 use super::schemas::{GetModFilesParameters, SearchModsParameters};
 use super::schemas::{ListResponse, PaginationResponse};
+use super::schemas::{MinecraftGameVersion, MinecraftModLoaderIndex, MinecraftModLoaderVersion};
 
 /// 游戏API
 pub trait GamesApi {
@@ -23,6 +31,10 @@ pub trait GamesApi {
     /// 获取版本
     fn get_versions(&self, game_id: u32) -> CurseForgeResult<ListResponse<GameVersionsByType>>;
 
+    /// 获取版本（v2），包含每个版本的编号和别名
+    fn get_versions_v2(&self, game_id: u32)
+    -> CurseForgeResult<ListResponse<GameVersionsByTypeV2>>;
+
     /// 获取类型分组后的版本
     fn get_version_types(&self, game_id: u32) -> CurseForgeResult<ListResponse<GameVersionType>>;
 }
@@ -48,6 +60,24 @@ pub trait ModsApi {
 
     /// 获取模组
     fn get_mod(&self, mod_id: u32) -> CurseForgeResult<DataResponse<Mod>>;
+
+    /// 获取模组的完整描述（HTML）
+    fn get_mod_description(&self, mod_id: u32) -> CurseForgeResult<DataResponse<String>>;
+
+    /// 批量获取模组，结果按`mod_ids`的顺序排列；编号较多时自动分批请求
+    fn get_mods(
+        &self,
+        mod_ids: &[u32],
+        filter_pc_only: Option<bool>,
+    ) -> CurseForgeResult<BatchResponse<Vec<Mod>>>;
+
+    /// 获取推荐、热门和最近更新的模组
+    fn get_featured_mods(
+        &self,
+        game_id: u32,
+        excluded_mod_ids: &[u32],
+        game_version_type_id: Option<u32>,
+    ) -> CurseForgeResult<DataResponse<FeaturedModsResponse>>;
 }
 
 /// 模组文件API
@@ -55,10 +85,271 @@ pub trait FilesApi {
     /// 获取模组文件
     fn get_mod_file(&self, mod_id: u32, file_id: u32) -> CurseForgeResult<DataResponse<File>>;
 
+    /// 获取模组文件的更新日志（HTML）
+    fn get_mod_file_changelog(
+        &self,
+        mod_id: u32,
+        file_id: u32,
+    ) -> CurseForgeResult<DataResponse<String>>;
+
+    /// 获取模组文件的下载地址，作者不允许第三方分发时为空
+    fn get_mod_file_download_url(
+        &self,
+        mod_id: u32,
+        file_id: u32,
+    ) -> CurseForgeResult<DataResponse<Option<String>>>;
+
     /// 搜索模组文件
     fn get_mod_files(
         &self,
         mod_id: u32,
         options: GetModFilesParameters,
     ) -> CurseForgeResult<PaginationResponse<File>>;
+
+    /// 批量获取文件，结果以文件编号为键；编号较多时自动分批请求
+    fn get_files(&self, file_ids: &[u32]) -> CurseForgeResult<BatchResponse<HashMap<u32, File>>>;
+}
+
+/// 指纹匹配API
+pub trait FingerprintsApi {
+    /// 按指纹精确匹配文件
+    fn get_fingerprint_matches(
+        &self,
+        fingerprints: &[u32],
+    ) -> CurseForgeResult<DataResponse<FingerprintsMatchesResult>>;
+
+    /// 在指定游戏中按指纹精确匹配文件
+    fn get_fingerprint_matches_by_game(
+        &self,
+        game_id: u32,
+        fingerprints: &[u32],
+    ) -> CurseForgeResult<DataResponse<FingerprintsMatchesResult>>;
+
+    /// 按文件夹指纹模糊匹配文件
+    fn get_fingerprint_fuzzy_matches(
+        &self,
+        game_id: u32,
+        fingerprints: &[FolderFingerprint],
+    ) -> CurseForgeResult<DataResponse<FingerprintFuzzyMatchResult>>;
+
+    /// 在指定游戏中按文件夹指纹模糊匹配文件
+    fn get_fingerprint_fuzzy_matches_by_game(
+        &self,
+        game_id: u32,
+        fingerprints: &[FolderFingerprint],
+    ) -> CurseForgeResult<DataResponse<FingerprintFuzzyMatchResult>>;
+}
+
+/// Minecraft专用API
+pub trait MinecraftApi {
+    /// 获取所有Minecraft版本
+    fn get_minecraft_versions(
+        &self,
+        sort_descending: Option<bool>,
+    ) -> CurseForgeResult<ListResponse<MinecraftGameVersion>>;
+
+    /// 获取指定的Minecraft版本，如`1.20.1`
+    fn get_minecraft_version(
+        &self,
+        game_version: &str,
+    ) -> CurseForgeResult<DataResponse<MinecraftGameVersion>>;
+
+    /// 获取模组加载器列表，可按Minecraft版本筛选
+    ///
+    /// `include_all`为`false`时只返回每个版本的最新版和推荐版
+    fn get_minecraft_mod_loaders(
+        &self,
+        version: Option<&str>,
+        include_all: Option<bool>,
+    ) -> CurseForgeResult<ListResponse<MinecraftModLoaderIndex>>;
+
+    /// 获取指定的模组加载器，如`forge-47.2.0`
+    fn get_minecraft_mod_loader(
+        &self,
+        mod_loader_name: &str,
+    ) -> CurseForgeResult<DataResponse<MinecraftModLoaderVersion>>;
+}
+
+/// 游戏API（异步）
+#[cfg(feature = "async")]
+pub trait AsyncGamesApi {
+    /// 获取游戏列表
+    fn get_games(
+        &self,
+        index: Option<u32>,
+        page_size: Option<u32>,
+    ) -> impl Future<Output = CurseForgeResult<PaginationResponse<Game>>> + Send;
+
+    /// 获取指定游戏
+    fn get_game(
+        &self,
+        game_id: u32,
+    ) -> impl Future<Output = CurseForgeResult<DataResponse<Game>>> + Send;
+
+    /// 获取版本
+    fn get_versions(
+        &self,
+        game_id: u32,
+    ) -> impl Future<Output = CurseForgeResult<ListResponse<GameVersionsByType>>> + Send;
+
+    /// 获取版本（v2），包含每个版本的编号和别名
+    fn get_versions_v2(
+        &self,
+        game_id: u32,
+    ) -> impl Future<Output = CurseForgeResult<ListResponse<GameVersionsByTypeV2>>> + Send;
+
+    /// 获取类型分组后的版本
+    fn get_version_types(
+        &self,
+        game_id: u32,
+    ) -> impl Future<Output = CurseForgeResult<ListResponse<GameVersionType>>> + Send;
+}
+
+/// 分类API（异步）
+#[cfg(feature = "async")]
+pub trait AsyncCategoriesApi {
+    /// 获取分类
+    fn get_categories(
+        &self,
+        game_id: u32,
+        class_id: Option<u32>,
+        classes_only: Option<bool>,
+    ) -> impl Future<Output = CurseForgeResult<ListResponse<Category>>> + Send;
+}
+
+/// 模组API（异步）
+#[cfg(feature = "async")]
+pub trait AsyncModsApi {
+    /// 搜索模组
+    fn search_mods(
+        &self,
+        params: SearchModsParameters,
+    ) -> impl Future<Output = CurseForgeResult<PaginationResponse<Mod>>> + Send;
+
+    /// 获取模组
+    fn get_mod(
+        &self,
+        mod_id: u32,
+    ) -> impl Future<Output = CurseForgeResult<DataResponse<Mod>>> + Send;
+
+    /// 获取模组的完整描述（HTML）
+    fn get_mod_description(
+        &self,
+        mod_id: u32,
+    ) -> impl Future<Output = CurseForgeResult<DataResponse<String>>> + Send;
+
+    /// 批量获取模组，结果按`mod_ids`的顺序排列；编号较多时自动分批请求
+    fn get_mods(
+        &self,
+        mod_ids: &[u32],
+        filter_pc_only: Option<bool>,
+    ) -> impl Future<Output = CurseForgeResult<BatchResponse<Vec<Mod>>>> + Send;
+
+    /// 获取推荐、热门和最近更新的模组
+    fn get_featured_mods(
+        &self,
+        game_id: u32,
+        excluded_mod_ids: &[u32],
+        game_version_type_id: Option<u32>,
+    ) -> impl Future<Output = CurseForgeResult<DataResponse<FeaturedModsResponse>>> + Send;
+}
+
+/// 模组文件API（异步）
+#[cfg(feature = "async")]
+pub trait AsyncFilesApi {
+    /// 获取模组文件
+    fn get_mod_file(
+        &self,
+        mod_id: u32,
+        file_id: u32,
+    ) -> impl Future<Output = CurseForgeResult<DataResponse<File>>> + Send;
+
+    /// 获取模组文件的更新日志（HTML）
+    fn get_mod_file_changelog(
+        &self,
+        mod_id: u32,
+        file_id: u32,
+    ) -> impl Future<Output = CurseForgeResult<DataResponse<String>>> + Send;
+
+    /// 获取模组文件的下载地址，作者不允许第三方分发时为空
+    fn get_mod_file_download_url(
+        &self,
+        mod_id: u32,
+        file_id: u32,
+    ) -> impl Future<Output = CurseForgeResult<DataResponse<Option<String>>>> + Send;
+
+    /// 搜索模组文件
+    fn get_mod_files(
+        &self,
+        mod_id: u32,
+        options: GetModFilesParameters,
+    ) -> impl Future<Output = CurseForgeResult<PaginationResponse<File>>> + Send;
+
+    /// 批量获取文件，结果以文件编号为键；编号较多时自动分批请求
+    fn get_files(
+        &self,
+        file_ids: &[u32],
+    ) -> impl Future<Output = CurseForgeResult<BatchResponse<HashMap<u32, File>>>> + Send;
+}
+
+/// 指纹匹配API（异步）
+#[cfg(feature = "async")]
+pub trait AsyncFingerprintsApi {
+    /// 按指纹精确匹配文件
+    fn get_fingerprint_matches(
+        &self,
+        fingerprints: &[u32],
+    ) -> impl Future<Output = CurseForgeResult<DataResponse<FingerprintsMatchesResult>>> + Send;
+
+    /// 在指定游戏中按指纹精确匹配文件
+    fn get_fingerprint_matches_by_game(
+        &self,
+        game_id: u32,
+        fingerprints: &[u32],
+    ) -> impl Future<Output = CurseForgeResult<DataResponse<FingerprintsMatchesResult>>> + Send;
+
+    /// 按文件夹指纹模糊匹配文件
+    fn get_fingerprint_fuzzy_matches(
+        &self,
+        game_id: u32,
+        fingerprints: &[FolderFingerprint],
+    ) -> impl Future<Output = CurseForgeResult<DataResponse<FingerprintFuzzyMatchResult>>> + Send;
+
+    /// 在指定游戏中按文件夹指纹模糊匹配文件
+    fn get_fingerprint_fuzzy_matches_by_game(
+        &self,
+        game_id: u32,
+        fingerprints: &[FolderFingerprint],
+    ) -> impl Future<Output = CurseForgeResult<DataResponse<FingerprintFuzzyMatchResult>>> + Send;
+}
+
+/// Minecraft专用API（异步）
+#[cfg(feature = "async")]
+pub trait AsyncMinecraftApi {
+    /// 获取所有Minecraft版本
+    fn get_minecraft_versions(
+        &self,
+        sort_descending: Option<bool>,
+    ) -> impl Future<Output = CurseForgeResult<ListResponse<MinecraftGameVersion>>> + Send;
+
+    /// 获取指定的Minecraft版本，如`1.20.1`
+    fn get_minecraft_version(
+        &self,
+        game_version: &str,
+    ) -> impl Future<Output = CurseForgeResult<DataResponse<MinecraftGameVersion>>> + Send;
+
+    /// 获取模组加载器列表，可按Minecraft版本筛选
+    ///
+    /// `include_all`为`false`时只返回每个版本的最新版和推荐版
+    fn get_minecraft_mod_loaders(
+        &self,
+        version: Option<&str>,
+        include_all: Option<bool>,
+    ) -> impl Future<Output = CurseForgeResult<ListResponse<MinecraftModLoaderIndex>>> + Send;
+
+    /// 获取指定的模组加载器，如`forge-47.2.0`
+    fn get_minecraft_mod_loader(
+        &self,
+        mod_loader_name: &str,
+    ) -> impl Future<Output = CurseForgeResult<DataResponse<MinecraftModLoaderVersion>>> + Send;
 }
@@ -0,0 +1,549 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::curseforge::error::{CurseForgeResult, Error};
+use crate::curseforge::schemas::*;
+
+use super::super::api::{
+    AsyncCategoriesApi, AsyncFilesApi, AsyncFingerprintsApi, AsyncGamesApi, AsyncMinecraftApi,
+    AsyncModsApi,
+};
+use super::batch;
+use super::cache::{self, Plan, ResponseCache};
+use super::endpoints::{self, Endpoint};
+use super::middleware::MiddlewareChain;
+use super::rate_limit::RateLimiter;
+use super::retry::Retry;
+use super::transport::{
+    AsyncReqwestTransport, AsyncTransport, HttpRequest, HttpResponse, complete, request_span,
+};
+use super::{ClientBuilder, ClientConfig, RetryPolicy};
+use serde::de::DeserializeOwned;
+use tracing::Instrument;
+
+/// 异步客户端
+#[derive(Clone)]
+pub struct AsyncClient {
+    base_url: String,
+    api_key: String,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    cache: Option<Arc<ResponseCache>>,
+    middlewares: MiddlewareChain,
+    transport: Arc<dyn AsyncTransport>,
+}
+
+impl fmt::Debug for AsyncClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncClient")
+            .field("base_url", &self.base_url)
+            .field("api_key", &"<redacted>")
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("cache", &self.cache)
+            .field("middlewares", &self.middlewares.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl AsyncClient {
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    pub fn new(base_url: &str, api_key: &str) -> CurseForgeResult<Self> {
+        Self::from_config(ClientConfig::new(base_url, api_key))
+    }
+
+    pub(crate) fn from_config(config: ClientConfig) -> CurseForgeResult<Self> {
+        #[cfg(feature = "blocking")]
+        if config.transport.is_some() {
+            return Err(Error::Config(String::from(
+                "blocking transports are only supported by Client, use async_transport instead",
+            )));
+        }
+        let transport = match config.async_transport {
+            Some(factory) => factory(&config.http)?,
+            None => Arc::new(AsyncReqwestTransport::with_options(&config.http)?),
+        };
+        Ok(Self {
+            base_url: config.base_url,
+            api_key: config.api_key,
+            retry_policy: config.retry_policy,
+            rate_limiter: config.rate_limiter,
+            cache: config.cache,
+            middlewares: config.middlewares,
+            transport,
+        })
+    }
+
+    /// 发送请求并解析响应，记录请求日志
+    async fn call<T>(&self, endpoint: Endpoint) -> CurseForgeResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        let request = endpoint.request(&self.base_url, &self.api_key)?;
+        let idempotent = endpoint.idempotent;
+        let span = request_span(&endpoint.path, &request);
+        async move {
+            let started = Instant::now();
+            complete(started, self.execute(request, idempotent).await)
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// 发送请求并返回成功响应的响应体，GET请求优先使用缓存，离线模式下不发送其他请求
+    async fn execute(&self, request: HttpRequest, idempotent: bool) -> CurseForgeResult<Vec<u8>> {
+        let cache = self.cache.clone();
+        let (plan, request) = self
+            .unblock(move || {
+                let mut request = request;
+                let plan = cache::plan(cache.as_ref(), &mut request)?;
+                Ok((plan, request))
+            })
+            .await?;
+        match plan {
+            Plan::Cached(body) => Ok(body),
+            Plan::Send(pending) => {
+                let response = self.send(request, idempotent).await?;
+                self.unblock(move || pending.finish(response)).await
+            }
+        }
+    }
+
+    /// 执行缓存操作；需要读写缓存文件时放到阻塞线程池中，避免阻塞异步运行时
+    async fn unblock<T, F>(&self, f: F) -> CurseForgeResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> CurseForgeResult<T> + Send + 'static,
+    {
+        if !self.cache.as_ref().is_some_and(|cache| cache.uses_files()) {
+            return f();
+        }
+        match tokio::task::spawn_blocking(f).await {
+            Ok(result) => result,
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            Err(e) => Err(Error::Transport(Box::new(e))),
+        }
+    }
+
+    /// 发送请求，按重试策略重试失败的幂等请求，返回成功或304响应
+    async fn send(&self, request: HttpRequest, idempotent: bool) -> CurseForgeResult<HttpResponse> {
+        let mut attempt = 1;
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                tokio::time::sleep(rate_limiter.reserve()).await;
+            }
+
+            let mut current = request.clone();
+            self.middlewares.before(&mut current)?;
+            let started = Instant::now();
+            let result = self.transport.send(current.clone()).await;
+            self.middlewares.after(&current, &result, started.elapsed());
+
+            match self.retry_policy.next(idempotent, attempt, result) {
+                Retry::Done(result) => return result,
+                Retry::After(backoff) => tokio::time::sleep(backoff).await,
+            }
+            attempt += 1;
+        }
+    }
+}
+
+impl AsyncGamesApi for AsyncClient {
+    async fn get_games(
+        &self,
+        index: Option<u32>,
+        page_size: Option<u32>,
+    ) -> CurseForgeResult<PaginationResponse<Game>> {
+        self.call(endpoints::get_games(index, page_size)?).await
+    }
+
+    async fn get_game(&self, game_id: u32) -> CurseForgeResult<DataResponse<Game>> {
+        self.call(endpoints::get_game(game_id)).await
+    }
+
+    async fn get_versions(
+        &self,
+        game_id: u32,
+    ) -> CurseForgeResult<ListResponse<GameVersionsByType>> {
+        self.call(endpoints::get_versions(game_id)).await
+    }
+
+    async fn get_versions_v2(
+        &self,
+        game_id: u32,
+    ) -> CurseForgeResult<ListResponse<GameVersionsByTypeV2>> {
+        self.call(endpoints::get_versions_v2(game_id)).await
+    }
+
+    async fn get_version_types(
+        &self,
+        game_id: u32,
+    ) -> CurseForgeResult<ListResponse<GameVersionType>> {
+        self.call(endpoints::get_version_types(game_id)).await
+    }
+}
+
+impl AsyncCategoriesApi for AsyncClient {
+    async fn get_categories(
+        &self,
+        game_id: u32,
+        class_id: Option<u32>,
+        classes_only: Option<bool>,
+    ) -> CurseForgeResult<ListResponse<Category>> {
+        self.call(endpoints::get_categories(game_id, class_id, classes_only)?)
+            .await
+    }
+}
+
+impl AsyncModsApi for AsyncClient {
+    async fn search_mods(
+        &self,
+        params: SearchModsParameters,
+    ) -> CurseForgeResult<PaginationResponse<Mod>> {
+        self.call(endpoints::search_mods(&params)?).await
+    }
+
+    async fn get_mod(&self, mod_id: u32) -> CurseForgeResult<DataResponse<Mod>> {
+        self.call(endpoints::get_mod(mod_id)).await
+    }
+
+    async fn get_mod_description(&self, mod_id: u32) -> CurseForgeResult<DataResponse<String>> {
+        self.call(endpoints::get_mod_description(mod_id)).await
+    }
+
+    async fn get_mods(
+        &self,
+        mod_ids: &[u32],
+        filter_pc_only: Option<bool>,
+    ) -> CurseForgeResult<BatchResponse<Vec<Mod>>> {
+        let mut mods = vec![];
+        for endpoint in endpoints::get_mods(mod_ids, filter_pc_only)? {
+            let response: ListResponse<Mod> = self.call(endpoint).await?;
+            mods.extend(response.data);
+        }
+        Ok(batch::in_request_order(mod_ids, mods, |m| m.id))
+    }
+
+    async fn get_featured_mods(
+        &self,
+        game_id: u32,
+        excluded_mod_ids: &[u32],
+        game_version_type_id: Option<u32>,
+    ) -> CurseForgeResult<DataResponse<FeaturedModsResponse>> {
+        let endpoint =
+            endpoints::get_featured_mods(game_id, excluded_mod_ids, game_version_type_id)?;
+        self.call(endpoint).await
+    }
+}
+
+impl AsyncFilesApi for AsyncClient {
+    async fn get_mod_file(
+        &self,
+        mod_id: u32,
+        file_id: u32,
+    ) -> CurseForgeResult<DataResponse<File>> {
+        self.call(endpoints::get_mod_file(mod_id, file_id)).await
+    }
+
+    async fn get_mod_file_changelog(
+        &self,
+        mod_id: u32,
+        file_id: u32,
+    ) -> CurseForgeResult<DataResponse<String>> {
+        self.call(endpoints::get_mod_file_changelog(mod_id, file_id))
+            .await
+    }
+
+    async fn get_mod_file_download_url(
+        &self,
+        mod_id: u32,
+        file_id: u32,
+    ) -> CurseForgeResult<DataResponse<Option<String>>> {
+        self.call(endpoints::get_mod_file_download_url(mod_id, file_id))
+            .await
+    }
+
+    async fn get_mod_files(
+        &self,
+        mod_id: u32,
+        options: GetModFilesParameters,
+    ) -> CurseForgeResult<PaginationResponse<File>> {
+        self.call(endpoints::get_mod_files(mod_id, &options)?).await
+    }
+
+    async fn get_files(
+        &self,
+        file_ids: &[u32],
+    ) -> CurseForgeResult<BatchResponse<HashMap<u32, File>>> {
+        let mut files = vec![];
+        for endpoint in endpoints::get_files(file_ids)? {
+            let response: ListResponse<File> = self.call(endpoint).await?;
+            files.extend(response.data);
+        }
+        Ok(batch::by_id(file_ids, files, |f| f.id))
+    }
+}
+
+impl AsyncFingerprintsApi for AsyncClient {
+    async fn get_fingerprint_matches(
+        &self,
+        fingerprints: &[u32],
+    ) -> CurseForgeResult<DataResponse<FingerprintsMatchesResult>> {
+        self.call(endpoints::get_fingerprint_matches(None, fingerprints)?)
+            .await
+    }
+
+    async fn get_fingerprint_matches_by_game(
+        &self,
+        game_id: u32,
+        fingerprints: &[u32],
+    ) -> CurseForgeResult<DataResponse<FingerprintsMatchesResult>> {
+        self.call(endpoints::get_fingerprint_matches(
+            Some(game_id),
+            fingerprints,
+        )?)
+        .await
+    }
+
+    async fn get_fingerprint_fuzzy_matches(
+        &self,
+        game_id: u32,
+        fingerprints: &[FolderFingerprint],
+    ) -> CurseForgeResult<DataResponse<FingerprintFuzzyMatchResult>> {
+        let endpoint = endpoints::get_fingerprint_fuzzy_matches(game_id, false, fingerprints)?;
+        self.call(endpoint).await
+    }
+
+    async fn get_fingerprint_fuzzy_matches_by_game(
+        &self,
+        game_id: u32,
+        fingerprints: &[FolderFingerprint],
+    ) -> CurseForgeResult<DataResponse<FingerprintFuzzyMatchResult>> {
+        let endpoint = endpoints::get_fingerprint_fuzzy_matches(game_id, true, fingerprints)?;
+        self.call(endpoint).await
+    }
+}
+
+impl AsyncMinecraftApi for AsyncClient {
+    async fn get_minecraft_versions(
+        &self,
+        sort_descending: Option<bool>,
+    ) -> CurseForgeResult<ListResponse<MinecraftGameVersion>> {
+        self.call(endpoints::get_minecraft_versions(sort_descending)?)
+            .await
+    }
+
+    async fn get_minecraft_version(
+        &self,
+        game_version: &str,
+    ) -> CurseForgeResult<DataResponse<MinecraftGameVersion>> {
+        self.call(endpoints::get_minecraft_version(game_version)?)
+            .await
+    }
+
+    async fn get_minecraft_mod_loaders(
+        &self,
+        version: Option<&str>,
+        include_all: Option<bool>,
+    ) -> CurseForgeResult<ListResponse<MinecraftModLoaderIndex>> {
+        self.call(endpoints::get_minecraft_mod_loaders(version, include_all)?)
+            .await
+    }
+
+    async fn get_minecraft_mod_loader(
+        &self,
+        mod_loader_name: &str,
+    ) -> CurseForgeResult<DataResponse<MinecraftModLoaderVersion>> {
+        self.call(endpoints::get_minecraft_mod_loader(mod_loader_name)?)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::curseforge::client::testing::{Counter, MINECRAFT, serve};
+    use crate::curseforge::client::{
+        CacheOptions, CassetteTransport, Fault, FaultTransport, FixtureTransport,
+    };
+    use crate::curseforge::error::Error;
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+
+    fn client(base_url: &str) -> ClientBuilder {
+        AsyncClient::builder().base_url(base_url).api_key("test")
+    }
+
+    #[tokio::test]
+    async fn test_retry_and_middleware() {
+        let (base_url, requests) = serve(vec![(503, ""), (429, ""), (200, MINECRAFT)]);
+        let counter = Counter::default();
+        let responses = counter.responses.clone();
+        let client = client(&base_url)
+            .retry_policy(RetryPolicy::new().initial_backoff(Duration::from_millis(1)))
+            .middleware(counter)
+            .build_async()
+            .unwrap();
+
+        assert_eq!(client.get_game(432).await.unwrap().data.id, 432);
+        assert_eq!(requests.lock().unwrap().len(), 3);
+        assert_eq!(responses.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_exhausted() {
+        let (base_url, _) = serve(vec![(503, ""), (503, "")]);
+        let client = client(&base_url)
+            .retry_policy(
+                RetryPolicy::new()
+                    .max_attempts(2)
+                    .initial_backoff(Duration::ZERO),
+            )
+            .build_async()
+            .unwrap();
+
+        let result = client.get_game(432).await;
+        assert!(matches!(result, Err(Error::Status { status: 503, .. })));
+    }
+
+    #[tokio::test]
+    async fn test_retry_dropped_connection() {
+        let (base_url, requests) = serve(vec![(0, ""), (200, MINECRAFT)]);
+        let client = client(&base_url)
+            .retry_policy(RetryPolicy::new().initial_backoff(Duration::ZERO))
+            .build_async()
+            .unwrap();
+
+        assert_eq!(client.get_game(432).await.unwrap().data.id, 432);
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_wrap_transport() {
+        let (base_url, requests) = serve(vec![(200, MINECRAFT)]);
+        let client = client(&base_url)
+            .wrap_async_transport(|inner| FaultTransport::new(inner, 42))
+            .user_agent("wrapped/1.0")
+            .build_async()
+            .unwrap();
+
+        assert_eq!(client.get_game(432).await.unwrap().data.id, 432);
+        let requests = requests.lock().unwrap().clone();
+        assert!(
+            requests[0]
+                .to_lowercase()
+                .contains("user-agent: wrapped/1.0\r\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cache() {
+        let (base_url, requests) = serve(vec![(200, MINECRAFT)]);
+        let cached = client(&base_url)
+            .cache(CacheOptions::new())
+            .build_async()
+            .unwrap();
+
+        assert_eq!(cached.get_game(432).await.unwrap().data.id, 432);
+        assert_eq!(cached.get_game(432).await.unwrap().data.id, 432);
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /v1/games/432 HTTP/1.1\r\n"));
+
+        let offline = client(&base_url)
+            .cache(CacheOptions::new().offline(true))
+            .build_async()
+            .unwrap();
+        let result = offline.get_game(432).await;
+        assert!(matches!(result, Err(Error::Offline(_))));
+        let result = offline.get_mods(&[1], None).await;
+        assert!(matches!(result, Err(Error::Offline(_))));
+    }
+
+    #[tokio::test]
+    async fn test_persistent_cache() {
+        let directory = std::env::temp_dir().join("curseforge-api-test-async-cache");
+        let _ = std::fs::remove_dir_all(&directory);
+        let (base_url, requests) = serve(vec![(200, MINECRAFT)]);
+        let cached = client(&base_url)
+            .cache(CacheOptions::new().directory(&directory))
+            .build_async()
+            .unwrap();
+        assert_eq!(cached.get_game(432).await.unwrap().data.id, 432);
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        let offline = client(&base_url)
+            .cache(CacheOptions::new().directory(&directory).offline(true))
+            .build_async()
+            .unwrap();
+        assert_eq!(offline.get_game(432).await.unwrap().data.id, 432);
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit() {
+        let (base_url, requests) = serve(vec![(200, MINECRAFT); 3]);
+        let client = client(&base_url).rate_limit(20.0, 1).build_async().unwrap();
+
+        let started = std::time::Instant::now();
+        for _ in 0..3 {
+            client.get_game(432).await.unwrap();
+        }
+        // 1个突发，其余2个每个等待50毫秒
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        assert_eq!(requests.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_transport() {
+        let transport = FixtureTransport::new().json("/v1/games/432", 503, "").json(
+            "/v1/games/432",
+            200,
+            MINECRAFT,
+        );
+        let client = client("http://localhost")
+            .retry_policy(RetryPolicy::new().initial_backoff(Duration::ZERO))
+            .async_transport(transport.clone())
+            .build_async()
+            .unwrap();
+
+        assert_eq!(client.get_game(432).await.unwrap().data.id, 432);
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].headers.get("x-api-key").unwrap(), "test");
+    }
+
+    #[tokio::test]
+    async fn test_fault_and_cassette_transports() {
+        let fixtures = FixtureTransport::new().json("/v1/games/432", 200, MINECRAFT);
+        let faults = FaultTransport::new(fixtures, 1).fault(Fault::Status(500), 1.0);
+        let faulty = client("http://localhost")
+            .async_transport(faults)
+            .build_async()
+            .unwrap();
+        let result = faulty.get_game(432).await;
+        assert!(matches!(result, Err(Error::Status { status: 500, .. })));
+
+        let path = std::env::temp_dir().join("curseforge-api-test-async-cassette.json");
+        let fixtures = FixtureTransport::new().json("/v1/games/432", 200, MINECRAFT);
+        let recorder = client("http://localhost")
+            .async_transport(CassetteTransport::record(&path, fixtures))
+            .build_async()
+            .unwrap();
+        assert_eq!(recorder.get_game(432).await.unwrap().data.id, 432);
+
+        let player = client("http://localhost")
+            .async_transport(CassetteTransport::replay(&path).unwrap())
+            .build_async()
+            .unwrap();
+        assert_eq!(player.get_game(432).await.unwrap().data.id, 432);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
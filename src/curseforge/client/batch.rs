@@ -0,0 +1,76 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::curseforge::schemas::BatchResponse;
+
+/// 单次批量请求的最大编号数量
+pub(crate) const BATCH_SIZE: usize = 100;
+
+/// 去除重复的编号，保持原有顺序
+pub(crate) fn dedup(ids: &[u32]) -> Vec<u32> {
+    let mut seen = HashSet::with_capacity(ids.len());
+    ids.iter().copied().filter(|id| seen.insert(*id)).collect()
+}
+
+/// 按请求的编号顺序排列结果，并找出服务器没有返回的编号
+pub(crate) fn in_request_order<T>(
+    ids: &[u32],
+    items: Vec<T>,
+    id: impl Fn(&T) -> u32,
+) -> BatchResponse<Vec<T>> {
+    let mut items: HashMap<u32, T> = items.into_iter().map(|item| (id(&item), item)).collect();
+    let mut data = Vec::with_capacity(ids.len());
+    let mut missing_ids = vec![];
+    for id in dedup(ids) {
+        match items.remove(&id) {
+            Some(item) => data.push(item),
+            None => missing_ids.push(id),
+        }
+    }
+    BatchResponse { data, missing_ids }
+}
+
+/// 以编号为键整理结果，并找出服务器没有返回的编号，丢弃没有请求的编号
+pub(crate) fn by_id<T>(
+    ids: &[u32],
+    items: Vec<T>,
+    id: impl Fn(&T) -> u32,
+) -> BatchResponse<HashMap<u32, T>> {
+    let requested: HashSet<u32> = ids.iter().copied().collect();
+    let data: HashMap<u32, T> = items
+        .into_iter()
+        .map(|item| (id(&item), item))
+        .filter(|(id, _)| requested.contains(id))
+        .collect();
+    let missing_ids = dedup(ids)
+        .into_iter()
+        .filter(|id| !data.contains_key(id))
+        .collect();
+    BatchResponse { data, missing_ids }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedup() {
+        assert_eq!(dedup(&[3, 1, 3, 2, 1]), [3, 1, 2]);
+        assert!(dedup(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_in_request_order() {
+        let response = in_request_order(&[3, 1, 2, 3, 9], vec![1, 2, 3, 7], |id| *id);
+        assert_eq!(response.data, [3, 1, 2]);
+        assert_eq!(response.missing_ids, [9]);
+    }
+
+    #[test]
+    fn test_by_id() {
+        let response = by_id(&[3, 1, 9, 3], vec![1, 3, 7], |id| *id);
+        assert_eq!(response.data.len(), 2);
+        assert_eq!(response.data[&3], 3);
+        assert!(!response.data.contains_key(&7));
+        assert_eq!(response.missing_ids, [9]);
+    }
+}
@@ -1,17 +1,125 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "async")]
+use super::AsyncClient;
+#[cfg(feature = "blocking")]
 use super::Client;
+use super::RetryPolicy;
+use super::cache::{CacheOptions, ResponseCache};
+use super::http_options::HttpOptions;
+use super::middleware::{Middleware, MiddlewareChain};
+use super::rate_limit::RateLimiter;
+#[cfg(feature = "async")]
+use super::transport::{AsyncReqwestTransport, AsyncTransport};
+#[cfg(feature = "blocking")]
+use super::transport::{ReqwestTransport, Transport};
+use crate::curseforge::error::{CurseForgeResult, Error};
+
+/// 根据HTTP设置创建阻塞客户端的传输层
+#[cfg(feature = "blocking")]
+pub(crate) type TransportFactory =
+    Box<dyn FnOnce(&HttpOptions) -> CurseForgeResult<Arc<dyn Transport>> + Send>;
+
+/// 根据HTTP设置创建异步客户端的传输层
+#[cfg(feature = "async")]
+pub(crate) type AsyncTransportFactory =
+    Box<dyn FnOnce(&HttpOptions) -> CurseForgeResult<Arc<dyn AsyncTransport>> + Send>;
 
 pub struct ClientBuilder {
     base_url: Option<String>,
     api_key: Option<String>,
+    config_file: Option<PathBuf>,
+    retry_policy: RetryPolicy,
+    rate_limit: Option<(f64, u32)>,
+    cache: Option<CacheOptions>,
+    http: HttpOptions,
+    middlewares: MiddlewareChain,
+    #[cfg(feature = "blocking")]
+    transport: Option<TransportFactory>,
+    #[cfg(feature = "async")]
+    async_transport: Option<AsyncTransportFactory>,
+}
+
+/// 构建客户端所需的最终配置
+pub(crate) struct ClientConfig {
+    pub(crate) base_url: String,
+    pub(crate) api_key: String,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) rate_limiter: Option<Arc<RateLimiter>>,
+    pub(crate) cache: Option<Arc<ResponseCache>>,
+    pub(crate) http: HttpOptions,
+    pub(crate) middlewares: MiddlewareChain,
+    #[cfg(feature = "blocking")]
+    pub(crate) transport: Option<TransportFactory>,
+    #[cfg(feature = "async")]
+    pub(crate) async_transport: Option<AsyncTransportFactory>,
+}
+
+impl ClientConfig {
+    pub(crate) fn new(base_url: &str, api_key: &str) -> Self {
+        Self {
+            base_url: String::from(base_url),
+            api_key: String::from(api_key),
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None,
+            cache: None,
+            http: HttpOptions::default(),
+            middlewares: MiddlewareChain::default(),
+            #[cfg(feature = "blocking")]
+            transport: None,
+            #[cfg(feature = "async")]
+            async_transport: None,
+        }
+    }
 }
 
 const CURSE_FORGE_BASE_URL: &str = "https://api.curseforge.com";
 
+/// 读取API Key的环境变量
+const API_KEY_ENV: &str = "CURSE_FORGE_API_KEY";
+
+/// 默认配置文件（相对于用户配置目录），内容为API Key
+const API_KEY_FILE: &str = "curseforge-api/api_key";
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientBuilder")
+            .field("base_url", &self.base_url)
+            .field("api_key", &self.api_key.as_ref().map(|_| "<redacted>"))
+            .field("config_file", &self.config_file)
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limit", &self.rate_limit)
+            .field("cache", &self.cache)
+            .field("http", &self.http)
+            .field("middlewares", &self.middlewares.len())
+            .finish()
+    }
+}
+
 impl ClientBuilder {
     pub fn new() -> ClientBuilder {
         ClientBuilder {
             base_url: None,
             api_key: None,
+            config_file: None,
+            retry_policy: RetryPolicy::none(),
+            rate_limit: None,
+            cache: None,
+            http: HttpOptions::default(),
+            middlewares: MiddlewareChain::default(),
+            #[cfg(feature = "blocking")]
+            transport: None,
+            #[cfg(feature = "async")]
+            async_transport: None,
         }
     }
 
@@ -25,12 +133,301 @@ impl ClientBuilder {
         self
     }
 
-    pub fn build(self) -> Client {
+    /// 指定存放API Key的配置文件，默认为用户配置目录下的`curseforge-api/api_key`
+    pub fn config_file(mut self, config_file: impl AsRef<Path>) -> Self {
+        self.config_file = Some(config_file.as_ref().to_path_buf());
+        self
+    }
+
+    /// 设置重试策略，默认不重试
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// 启用客户端限流：每秒最多`requests_per_second`个请求，允许`burst`个突发请求，
+    /// 由同一客户端克隆出的所有实例共享
+    ///
+    /// `requests_per_second`不是正数时，构建客户端返回[`Error::Config`]。
+    pub fn rate_limit(mut self, requests_per_second: f64, burst: u32) -> Self {
+        self.rate_limit = Some((requests_per_second, burst));
+        self
+    }
+
+    /// 启用响应缓存，由同一客户端克隆出的所有实例共享
+    pub fn cache(mut self, cache: CacheOptions) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// 代理地址，支持`http://`、`https://`和`socks5://`，可带账号密码；
+    /// 启用系统代理时仍然遵循`NO_PROXY`环境变量
+    pub fn proxy(mut self, proxy: &str) -> Self {
+        self.http.proxy = Some(proxy.to_string());
+        self
+    }
+
+    /// 是否使用`HTTP_PROXY`、`HTTPS_PROXY`、`ALL_PROXY`和`NO_PROXY`环境变量，默认启用
+    pub fn system_proxy(mut self, system_proxy: bool) -> Self {
+        self.http.system_proxy = system_proxy;
+        self
+    }
+
+    /// 额外信任的根证书，PEM格式，可以包含多个证书
+    pub fn root_certificate(mut self, pem: &[u8]) -> Self {
+        self.http.root_certificates.push(pem.to_vec());
+        self
+    }
+
+    /// User-Agent，默认为`curseforge-api/<版本>`
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.http.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// 连接超时，默认3秒
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.http.connect_timeout = connect_timeout;
+        self
+    }
+
+    /// 单次请求的总超时（从连接到读完响应体），默认不限制
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.http.timeout = Some(timeout);
+        self
+    }
+
+    /// 每个请求都会带上的请求头，多次设置同名请求头时以最后一次为准
+    pub fn default_header(mut self, name: &str, value: &str) -> Self {
+        self.http
+            .default_headers
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// 添加中间件，按添加顺序执行
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// 替换阻塞客户端的传输层，默认为[`ReqwestTransport`]；
+    /// 自定义传输层不使用代理、证书、User-Agent、超时和默认请求头等HTTP设置，
+    /// 需要保留这些设置时请使用[`wrap_transport`](Self::wrap_transport)
+    ///
+    /// 设置后`build_async`返回[`Error::Config`]，异步客户端请使用
+    /// `async_transport`。
+    #[cfg(feature = "blocking")]
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Box::new(move |_| Ok(Arc::new(transport))));
+        self
+    }
+
+    /// 用按HTTP设置创建的[`ReqwestTransport`]构造阻塞客户端的传输层，
+    /// 用于在默认传输层外包装[`FaultTransport`](super::FaultTransport)、
+    /// [`CassetteTransport`](super::CassetteTransport)等
+    ///
+    /// `wrap`在构建客户端时调用，因此之后设置的HTTP选项同样生效。
+    /// 与`transport`互相覆盖，以最后一次调用为准。
+    #[cfg(feature = "blocking")]
+    pub fn wrap_transport<T, F>(mut self, wrap: F) -> Self
+    where
+        T: Transport + 'static,
+        F: FnOnce(ReqwestTransport) -> T + Send + 'static,
+    {
+        self.transport = Some(Box::new(move |http| {
+            Ok(Arc::new(wrap(ReqwestTransport::with_options(http)?)))
+        }));
+        self
+    }
+
+    /// 替换异步客户端的传输层，默认为[`AsyncReqwestTransport`]；
+    /// 自定义传输层不使用代理、证书、User-Agent、超时和默认请求头等HTTP设置，
+    /// 需要保留这些设置时请使用[`wrap_async_transport`](Self::wrap_async_transport)
+    ///
+    /// 设置后`build`返回[`Error::Config`]，阻塞客户端请使用`transport`。
+    #[cfg(feature = "async")]
+    pub fn async_transport(mut self, transport: impl AsyncTransport + 'static) -> Self {
+        self.async_transport = Some(Box::new(move |_| Ok(Arc::new(transport))));
+        self
+    }
+
+    /// 用按HTTP设置创建的[`AsyncReqwestTransport`]构造异步客户端的传输层，
+    /// 用法与阻塞客户端的`wrap_transport`相同
+    #[cfg(feature = "async")]
+    pub fn wrap_async_transport<T, F>(mut self, wrap: F) -> Self
+    where
+        T: AsyncTransport + 'static,
+        F: FnOnce(AsyncReqwestTransport) -> T + Send + 'static,
+    {
+        self.async_transport = Some(Box::new(move |http| {
+            Ok(Arc::new(wrap(AsyncReqwestTransport::with_options(http)?)))
+        }));
+        self
+    }
+
+    /// 构建阻塞客户端
+    #[cfg(feature = "blocking")]
+    pub fn build(self) -> CurseForgeResult<Client> {
+        Client::from_config(self.resolve()?)
+    }
+
+    /// 构建异步客户端
+    #[cfg(feature = "async")]
+    pub fn build_async(self) -> CurseForgeResult<AsyncClient> {
+        AsyncClient::from_config(self.resolve()?)
+    }
+
+    /// 解析出最终配置
+    fn resolve(self) -> CurseForgeResult<ClientConfig> {
         let base_url = self.base_url.unwrap_or(String::from(CURSE_FORGE_BASE_URL));
 
-        let default_api_key = String::from(env!("CURSE_FORGE_API_KEY"));
-        let api_key = self.api_key.unwrap_or(default_api_key);
+        let config_file = self
+            .config_file
+            .or_else(|| dirs::config_dir().map(|dir| dir.join(API_KEY_FILE)));
+        let api_key = resolve_api_key(
+            self.api_key,
+            std::env::var(API_KEY_ENV).ok(),
+            config_file.as_deref(),
+        )?;
+
+        if let Some((requests_per_second, _)) = self.rate_limit
+            && (!requests_per_second.is_finite() || requests_per_second <= 0.0)
+        {
+            return Err(Error::Config(format!(
+                "rate limit must be a positive number of requests per second, got {}",
+                requests_per_second
+            )));
+        }
+
+        let cache = self
+            .cache
+            .map(|options| Arc::new(ResponseCache::new(options, &base_url)));
+        Ok(ClientConfig {
+            base_url,
+            api_key,
+            retry_policy: self.retry_policy,
+            rate_limiter: self
+                .rate_limit
+                .map(|(rps, burst)| Arc::new(RateLimiter::new(rps, burst))),
+            cache,
+            http: self.http,
+            middlewares: self.middlewares,
+            #[cfg(feature = "blocking")]
+            transport: self.transport,
+            #[cfg(feature = "async")]
+            async_transport: self.async_transport,
+        })
+    }
+}
+
+/// 按顺序查找API Key：显式指定、环境变量、配置文件
+fn resolve_api_key(
+    explicit: Option<String>,
+    env: Option<String>,
+    config_file: Option<&Path>,
+) -> CurseForgeResult<String> {
+    let not_empty = |key: &String| !key.trim().is_empty();
+
+    if let Some(api_key) = explicit.filter(not_empty) {
+        return Ok(api_key.trim().to_string());
+    }
+    if let Some(api_key) = env.filter(not_empty) {
+        return Ok(api_key.trim().to_string());
+    }
+    if let Some(path) = config_file {
+        let content = std::fs::read_to_string(path).ok();
+        if let Some(api_key) = content.filter(not_empty) {
+            return Ok(api_key.trim().to_string());
+        }
+    }
+
+    Err(Error::Config(format!(
+        "no API key: call ClientBuilder::api_key, set {} or write it to {}",
+        API_KEY_ENV,
+        config_file
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| API_KEY_FILE.to_string()),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_api_key_order() {
+        let path = std::env::temp_dir().join("curseforge-api-test-api-key");
+        std::fs::write(&path, "from-file\n").unwrap();
+
+        let key = resolve_api_key(Some("explicit".into()), Some("env".into()), Some(&path));
+        assert_eq!(key.unwrap(), "explicit");
+
+        let key = resolve_api_key(None, Some("env".into()), Some(&path));
+        assert_eq!(key.unwrap(), "env");
+
+        let key = resolve_api_key(None, Some("".into()), Some(&path));
+        assert_eq!(key.unwrap(), "from-file");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_api_key_missing() {
+        let path = std::env::temp_dir().join("curseforge-api-test-missing-api-key");
+        let result = resolve_api_key(None, None, Some(&path));
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
+
+    #[test]
+    fn test_invalid_rate_limit() {
+        for requests_per_second in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let result = ClientBuilder::new()
+                .api_key("test")
+                .rate_limit(requests_per_second, 1)
+                .resolve();
+            assert!(matches!(result, Err(Error::Config(_))));
+        }
+        let builder = ClientBuilder::new().api_key("test").rate_limit(0.5, 1);
+        assert!(builder.resolve().is_ok());
+    }
+
+    #[test]
+    fn test_debug_redacts_api_key() {
+        let builder = ClientBuilder::new().api_key("secret-key");
+        assert!(!format!("{:?}", builder).contains("secret-key"));
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn test_client_debug_redacts_api_key() {
+        let client = ClientBuilder::new().api_key("secret-key").build().unwrap();
+        assert!(!format!("{:?}", client).contains("secret-key"));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_async_client_debug_redacts_api_key() {
+        let client = ClientBuilder::new()
+            .api_key("secret-key")
+            .build_async()
+            .unwrap();
+        assert!(!format!("{:?}", client).contains("secret-key"));
+    }
+
+    #[cfg(all(feature = "blocking", feature = "async"))]
+    #[test]
+    fn test_transport_for_the_other_client() {
+        let result = ClientBuilder::new()
+            .api_key("test")
+            .transport(super::super::FixtureTransport::new())
+            .build_async();
+        assert!(matches!(result, Err(Error::Config(_))));
 
-        Client::new(&base_url, &api_key)
+        let result = ClientBuilder::new()
+            .api_key("test")
+            .async_transport(super::super::FixtureTransport::new())
+            .build();
+        assert!(matches!(result, Err(Error::Config(_))));
     }
 }
@@ -0,0 +1,499 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::StatusCode;
+use reqwest::Url;
+use reqwest::header::{ETAG, HeaderMap, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+
+use super::transport::{HttpRequest, HttpResponse};
+use crate::curseforge::error::{CurseForgeResult, Error};
+
+/// 响应缓存配置
+#[derive(Debug, Clone)]
+pub struct CacheOptions {
+    default_ttl: Duration,
+    ttls: Vec<(String, Duration)>,
+    directory: Option<PathBuf>,
+    offline: bool,
+    capacity: usize,
+}
+
+impl Default for CacheOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CacheOptions {
+    /// 仅缓存在内存中，最多1000个响应，游戏和分类缓存1天，其余缓存10分钟
+    pub fn new() -> Self {
+        Self {
+            default_ttl: Duration::from_secs(10 * 60),
+            ttls: vec![
+                (String::from("/v1/games"), Duration::from_secs(24 * 60 * 60)),
+                (
+                    String::from("/v1/categories"),
+                    Duration::from_secs(24 * 60 * 60),
+                ),
+            ],
+            directory: None,
+            offline: false,
+            capacity: 1000,
+        }
+    }
+
+    /// 同时缓存到用户缓存目录下的`curseforge-api`目录
+    pub fn persistent() -> Self {
+        let options = Self::new();
+        match dirs::cache_dir() {
+            Some(dir) => options.directory(dir.join("curseforge-api")),
+            None => options,
+        }
+    }
+
+    /// 未单独指定时的缓存有效期
+    pub fn default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+
+    /// 为路径以`path_prefix`开头的接口指定缓存有效期，多个前缀匹配时取最长的
+    ///
+    /// 路径不包含`base_url`本身的路径，如`/v1/mods`。
+    pub fn ttl(mut self, path_prefix: &str, ttl: Duration) -> Self {
+        self.ttls.retain(|(prefix, _)| prefix != path_prefix);
+        self.ttls.push((path_prefix.to_string(), ttl));
+        self
+    }
+
+    /// 内存中最多保留的响应数量，超出时淘汰最久未使用的响应；
+    /// 为0时不使用内存缓存，缓存文件不受影响
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// 缓存文件目录
+    pub fn directory(mut self, directory: impl AsRef<Path>) -> Self {
+        self.directory = Some(directory.as_ref().to_path_buf());
+        self
+    }
+
+    /// 离线模式：只使用缓存（无论是否过期），没有缓存时返回[`Error::Offline`]
+    ///
+    /// 只有GET请求会被缓存，离线模式下其他请求总是返回[`Error::Offline`]。
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    fn ttl_for(&self, path: &str) -> Duration {
+        self.ttls
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, ttl)| *ttl)
+            .unwrap_or(self.default_ttl)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct CacheEntry {
+    url: String,
+    body: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    stored_at: u64,
+}
+
+impl CacheEntry {
+    /// 添加条件请求头，由服务器判断缓存是否仍然有效
+    fn apply_validators(&self, headers: &mut HeaderMap) {
+        if let Some(etag) = self.etag.as_ref().and_then(|v| v.parse().ok()) {
+            headers.insert(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = self.last_modified.as_ref().and_then(|v| v.parse().ok()) {
+            headers.insert(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    fn has_validators(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
+    }
+}
+
+/// 查询缓存的结果
+enum Lookup {
+    /// 缓存有效，直接使用
+    Fresh(Vec<u8>),
+
+    /// 缓存已过期，但可以向服务器确认是否仍然有效
+    Stale(CacheEntry),
+
+    /// 没有可用的缓存
+    Miss,
+}
+
+/// 发送请求前查找缓存的结果
+pub(crate) enum Plan {
+    /// 直接使用缓存的响应体
+    Cached(Vec<u8>),
+
+    /// 需要发送请求，收到响应后交给[`Pending::finish`]
+    Send(Pending),
+}
+
+/// 等待响应的缓存操作
+pub(crate) struct Pending {
+    cache: Option<Arc<ResponseCache>>,
+    url: Url,
+    cached: Option<CacheEntry>,
+}
+
+impl Pending {
+    /// 保存响应并返回响应体，304响应使用缓存的响应体
+    pub(crate) fn finish(self, response: HttpResponse) -> CurseForgeResult<Vec<u8>> {
+        match self.cache {
+            Some(cache) => cache.store(&self.url, self.cached, response),
+            None => Ok(response.body),
+        }
+    }
+}
+
+/// 发送请求前查找缓存：GET请求优先使用缓存，缓存过期时为请求加上验证头；
+/// 离线模式下不发送其他请求
+///
+/// 和[`Pending::finish`]一样，设置了缓存目录时会读写缓存文件。
+pub(crate) fn plan(
+    cache: Option<&Arc<ResponseCache>>,
+    request: &mut HttpRequest,
+) -> CurseForgeResult<Plan> {
+    let url = request.url.clone();
+    let cache = match cache {
+        Some(cache) if request.method == reqwest::Method::GET => cache,
+        Some(cache) if cache.options.offline => return Err(Error::Offline(url.to_string())),
+        _ => {
+            return Ok(Plan::Send(Pending {
+                cache: None,
+                url,
+                cached: None,
+            }));
+        }
+    };
+
+    let cached = match cache.lookup(&url)? {
+        Lookup::Fresh(body) => {
+            tracing::debug!("cache hit");
+            return Ok(Plan::Cached(body));
+        }
+        Lookup::Stale(entry) => {
+            entry.apply_validators(&mut request.headers);
+            Some(entry)
+        }
+        Lookup::Miss => None,
+    };
+    Ok(Plan::Send(Pending {
+        cache: Some(cache.clone()),
+        url,
+        cached,
+    }))
+}
+
+/// 内存中的缓存，超出容量时淘汰最久未使用的响应
+#[derive(Debug, Default)]
+struct Memory {
+    /// 以URL为键，值为缓存和最近一次使用的时刻
+    entries: HashMap<String, (CacheEntry, u64)>,
+    /// 每次读写递增的逻辑时钟
+    clock: u64,
+}
+
+impl Memory {
+    fn get(&mut self, url: &str) -> Option<CacheEntry> {
+        self.clock += 1;
+        let (entry, last_used) = self.entries.get_mut(url)?;
+        *last_used = self.clock;
+        Some(entry.clone())
+    }
+
+    fn insert(&mut self, entry: CacheEntry, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        self.clock += 1;
+        if !self.entries.contains_key(&entry.url) && self.entries.len() >= capacity {
+            let least_recent = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(url, _)| url.clone());
+            if let Some(url) = least_recent {
+                self.entries.remove(&url);
+            }
+        }
+        self.entries.insert(entry.url.clone(), (entry, self.clock));
+    }
+}
+
+/// 响应缓存，以完整URL（含查询参数）为键
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    options: CacheOptions,
+    /// `base_url`的路径，匹配有效期前从请求路径中去除
+    base_path: String,
+    memory: Mutex<Memory>,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(options: CacheOptions, base_url: &str) -> Self {
+        let base_path = Url::parse(base_url)
+            .map(|url| url.path().trim_end_matches('/').to_string())
+            .unwrap_or_default();
+        Self {
+            options,
+            base_path,
+            memory: Mutex::new(Memory::default()),
+        }
+    }
+
+    /// 是否读写缓存文件，文件操作会阻塞当前线程
+    #[cfg(feature = "async")]
+    pub(crate) fn uses_files(&self) -> bool {
+        self.options.directory.is_some()
+    }
+
+    /// 按接口路径（去除`base_url`的路径）确定的有效期
+    fn ttl_for(&self, url: &Url) -> Duration {
+        let path = url.path();
+        let path = path.strip_prefix(self.base_path.as_str()).unwrap_or(path);
+        self.options.ttl_for(path)
+    }
+
+    fn lookup(&self, url: &Url) -> CurseForgeResult<Lookup> {
+        let entry = self.load(url.as_str());
+        if self.options.offline {
+            return match entry {
+                Some(entry) => Ok(Lookup::Fresh(entry.body.into_bytes())),
+                None => Err(Error::Offline(url.to_string())),
+            };
+        }
+
+        let Some(entry) = entry else {
+            return Ok(Lookup::Miss);
+        };
+        let age = now().saturating_sub(entry.stored_at);
+        if age < self.ttl_for(url).as_secs() {
+            Ok(Lookup::Fresh(entry.body.into_bytes()))
+        } else if entry.has_validators() {
+            Ok(Lookup::Stale(entry))
+        } else {
+            Ok(Lookup::Miss)
+        }
+    }
+
+    /// 保存响应；收到304时刷新原有缓存的时间
+    fn store(
+        &self,
+        url: &Url,
+        cached: Option<CacheEntry>,
+        response: HttpResponse,
+    ) -> CurseForgeResult<Vec<u8>> {
+        let entry = match cached {
+            Some(mut entry) if response.status == StatusCode::NOT_MODIFIED => {
+                entry.stored_at = now();
+                entry
+            }
+            _ if response.status == StatusCode::NOT_MODIFIED => {
+                return Err(Error::status(response.status.as_u16(), &response.body));
+            }
+            _ => match String::from_utf8(response.body) {
+                Ok(body) => CacheEntry {
+                    url: url.to_string(),
+                    body,
+                    etag: header(&response.headers, ETAG),
+                    last_modified: header(&response.headers, LAST_MODIFIED),
+                    stored_at: now(),
+                },
+                Err(e) => return Ok(e.into_bytes()),
+            },
+        };
+
+        let body = entry.body.clone().into_bytes();
+        self.save(entry);
+        Ok(body)
+    }
+
+    fn load(&self, url: &str) -> Option<CacheEntry> {
+        let mut memory = self.memory.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = memory.get(url) {
+            return Some(entry);
+        }
+
+        let path = self.file_path(url)?;
+        let content = std::fs::read(path).ok()?;
+        let entry: CacheEntry = serde_json::from_slice(&content).ok()?;
+        if entry.url != url {
+            return None;
+        }
+        memory.insert(entry.clone(), self.options.capacity);
+        Some(entry)
+    }
+
+    /// 写入内存和磁盘；磁盘写入失败时仅保留内存缓存
+    fn save(&self, entry: CacheEntry) {
+        if let Some(path) = self.file_path(&entry.url)
+            && let Ok(content) = serde_json::to_vec(&entry)
+        {
+            let temp_path = path.with_extension("tmp");
+            let _ = std::fs::create_dir_all(path.parent().unwrap_or(Path::new(".")))
+                .and_then(|_| std::fs::write(&temp_path, content))
+                .and_then(|_| std::fs::rename(&temp_path, &path));
+        }
+
+        let mut memory = self.memory.lock().unwrap_or_else(|e| e.into_inner());
+        memory.insert(entry, self.options.capacity);
+    }
+
+    fn file_path(&self, url: &str) -> Option<PathBuf> {
+        let directory = self.options.directory.as_ref()?;
+        Some(directory.join(format!("{:016x}.json", fnv1a(url.as_bytes()))))
+    }
+}
+
+fn header(headers: &HeaderMap, name: reqwest::header::HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// 64位FNV-1a哈希，用于生成稳定的缓存文件名
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE_URL: &str = "https://api.curseforge.com";
+
+    fn url(path: &str) -> Url {
+        Url::parse(&format!("{}{}", BASE_URL, path)).unwrap()
+    }
+
+    fn response(status: u16, body: &str, etag: Option<&str>) -> HttpResponse {
+        let mut headers = HeaderMap::new();
+        if let Some(etag) = etag {
+            headers.insert(ETAG, etag.parse().unwrap());
+        }
+        HttpResponse {
+            status: StatusCode::from_u16(status).unwrap(),
+            headers,
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_ttl_longest_prefix() {
+        let options = CacheOptions::new()
+            .default_ttl(Duration::from_secs(1))
+            .ttl("/v1/mods", Duration::from_secs(2))
+            .ttl("/v1/mods/search", Duration::from_secs(3));
+        assert_eq!(options.ttl_for("/v1/games/432"), Duration::from_secs(86400));
+        assert_eq!(options.ttl_for("/v1/mods/238222"), Duration::from_secs(2));
+        assert_eq!(options.ttl_for("/v1/mods/search"), Duration::from_secs(3));
+        assert_eq!(options.ttl_for("/v1/fingerprints"), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_ttl_behind_base_path() {
+        let options = CacheOptions::new().default_ttl(Duration::from_secs(1));
+        let cache = ResponseCache::new(options, "https://mirror.example.com/curseforge/");
+        let url = Url::parse("https://mirror.example.com/curseforge/v1/games/432").unwrap();
+        assert_eq!(cache.ttl_for(&url), Duration::from_secs(86400));
+        let url = Url::parse("https://mirror.example.com/curseforge/v1/mods/1").unwrap();
+        assert_eq!(cache.ttl_for(&url), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_fresh_and_stale() {
+        let url = url("/v1/mods/238222");
+        let cache = ResponseCache::new(CacheOptions::new(), BASE_URL);
+        assert!(matches!(cache.lookup(&url), Ok(Lookup::Miss)));
+
+        let body = cache.store(&url, None, response(200, "{}", Some("\"v1\"")));
+        assert_eq!(body.unwrap(), b"{}");
+        assert!(matches!(cache.lookup(&url), Ok(Lookup::Fresh(body)) if body == b"{}"));
+
+        let cache = ResponseCache::new(CacheOptions::new().default_ttl(Duration::ZERO), BASE_URL);
+        cache
+            .store(&url, None, response(200, "{}", Some("\"v1\"")))
+            .unwrap();
+        let Ok(Lookup::Stale(entry)) = cache.lookup(&url) else {
+            panic!("entry should be stale");
+        };
+
+        let mut headers = HeaderMap::new();
+        entry.apply_validators(&mut headers);
+        assert_eq!(headers.get(IF_NONE_MATCH).unwrap(), "\"v1\"");
+
+        let body = cache.store(&url, Some(entry), response(304, "", None));
+        assert_eq!(body.unwrap(), b"{}");
+    }
+
+    #[test]
+    fn test_capacity() {
+        let cache = ResponseCache::new(CacheOptions::new().capacity(2), BASE_URL);
+        let (a, b, c) = (url("/v1/mods/1"), url("/v1/mods/2"), url("/v1/mods/3"));
+        cache.store(&a, None, response(200, "a", None)).unwrap();
+        cache.store(&b, None, response(200, "b", None)).unwrap();
+        assert!(matches!(cache.lookup(&a), Ok(Lookup::Fresh(_))));
+
+        cache.store(&c, None, response(200, "c", None)).unwrap();
+        assert!(matches!(cache.lookup(&a), Ok(Lookup::Fresh(_))));
+        assert!(matches!(cache.lookup(&b), Ok(Lookup::Miss)));
+        assert!(matches!(cache.lookup(&c), Ok(Lookup::Fresh(_))));
+
+        let cache = ResponseCache::new(CacheOptions::new().capacity(0), BASE_URL);
+        cache.store(&a, None, response(200, "a", None)).unwrap();
+        assert!(matches!(cache.lookup(&a), Ok(Lookup::Miss)));
+    }
+
+    #[test]
+    fn test_persistent_and_offline() {
+        let directory = std::env::temp_dir().join("curseforge-api-test-cache");
+        let _ = std::fs::remove_dir_all(&directory);
+        let url = url("/v1/games/432");
+
+        let cache = ResponseCache::new(CacheOptions::new().directory(&directory), BASE_URL);
+        cache
+            .store(&url, None, response(200, "{\"data\":{}}", None))
+            .unwrap();
+
+        let options = CacheOptions::new()
+            .directory(&directory)
+            .default_ttl(Duration::ZERO)
+            .ttl("/v1/games", Duration::ZERO)
+            .offline(true);
+        let cache = ResponseCache::new(options, BASE_URL);
+        assert!(matches!(cache.lookup(&url), Ok(Lookup::Fresh(body)) if body == b"{\"data\":{}}"));
+        assert!(matches!(
+            cache.lookup(&super::tests::url("/v1/games/1")),
+            Err(Error::Offline(_))
+        ));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
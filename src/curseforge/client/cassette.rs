@@ -0,0 +1,376 @@
+use std::convert::Infallible;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "blocking")]
+use super::transport::Transport;
+#[cfg(feature = "async")]
+use super::transport::{AsyncTransport, TransportFuture};
+use super::transport::{HttpRequest, HttpResponse};
+use crate::curseforge::error::{CurseForgeResult, Error};
+
+/// 录制时不保存的请求头
+const SCRUBBED_HEADERS: [&str; 2] = ["x-api-key", "authorization"];
+
+/// 录制/回放请求的传输层，用于在没有网络的环境下进行可重复的测试
+///
+/// 录制模式下通过内部传输层发送请求，并在每次请求后将请求和响应写入磁带文件，
+/// API Key等凭据不会被写入。回放模式下按请求方法、路径、查询参数和请求体匹配已录制的响应，
+/// 同一请求录制了多次时依次返回，最后一次会一直保留；
+/// 没有匹配的录制时返回[`Error::Transport`]，不会访问网络。
+///
+/// 录制时内部传输层实现`Transport`即可用于阻塞客户端，
+/// 实现`AsyncTransport`即可用于异步客户端；回放模式两者都可以使用。
+/// 录制时通过`ClientBuilder::wrap_transport`或`wrap_async_transport`
+/// 包装默认传输层，录制的请求会使用客户端的代理、超时等HTTP设置。
+///
+/// ```no_run
+/// # #[cfg(feature = "blocking")]
+/// # fn main() -> Result<(), curseforge_api::error::Error> {
+/// use curseforge_api::{CassetteTransport, Client};
+///
+/// let client = Client::builder()
+///     .proxy("http://127.0.0.1:7890")
+///     .wrap_transport(|inner| CassetteTransport::record("tests/cassettes/jei.json", inner))
+///     .build()?;
+///
+/// let transport = CassetteTransport::replay("tests/cassettes/jei.json")?;
+/// let client = Client::builder().api_key("unused").transport(transport).build()?;
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "blocking"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone)]
+pub struct CassetteTransport<T = Infallible> {
+    path: PathBuf,
+    /// 录制模式下的内部传输层，回放模式下为`None`
+    inner: Option<T>,
+    interactions: Arc<Mutex<Vec<Interaction>>>,
+}
+
+impl<T> std::fmt::Debug for CassetteTransport<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mode = match self.inner {
+            Some(_) => "record",
+            None => "replay",
+        };
+        f.debug_struct("CassetteTransport")
+            .field("path", &self.path)
+            .field("mode", &mode)
+            .finish_non_exhaustive()
+    }
+}
+
+/// 磁带文件内容
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Cassette {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl RecordedRequest {
+    fn new(request: &HttpRequest) -> Self {
+        let headers = request
+            .headers
+            .iter()
+            .filter(|(name, _)| !SCRUBBED_HEADERS.contains(&name.as_str()))
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        Self {
+            method: request.method.to_string(),
+            url: request.url.to_string(),
+            headers,
+            body: request
+                .body
+                .as_ref()
+                .map(|body| String::from_utf8_lossy(body).into_owned()),
+        }
+    }
+
+    /// 忽略主机名，比较方法、路径、查询参数和请求体
+    fn matches(&self, request: &HttpRequest) -> bool {
+        let Ok(url) = Url::parse(&self.url) else {
+            return false;
+        };
+        let body = request
+            .body
+            .as_ref()
+            .map(|body| String::from_utf8_lossy(body));
+        self.method == request.method.as_str()
+            && url.path() == request.url.path()
+            && url.query() == request.url.query()
+            && self.body.as_deref() == body.as_deref()
+    }
+}
+
+impl RecordedResponse {
+    fn new(response: &HttpResponse) -> Self {
+        Self {
+            status: response.status.as_u16(),
+            headers: response
+                .headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+            body: String::from_utf8_lossy(&response.body).into_owned(),
+        }
+    }
+
+    fn to_response(&self) -> CurseForgeResult<HttpResponse> {
+        let status = StatusCode::from_u16(self.status)
+            .map_err(|_| Error::Config(format!("invalid status {} in cassette", self.status)))?;
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(value),
+            ) {
+                headers.append(name, value);
+            }
+        }
+        Ok(HttpResponse {
+            status,
+            headers,
+            body: self.body.clone().into_bytes(),
+        })
+    }
+}
+
+impl<T> CassetteTransport<T> {
+    /// 录制模式，通过`inner`发送请求并写入`path`，已存在的文件会被覆盖
+    pub fn record(path: impl AsRef<Path>, inner: T) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            inner: Some(inner),
+            interactions: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    fn lock_interactions(&self) -> std::sync::MutexGuard<'_, Vec<Interaction>> {
+        self.interactions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 返回与请求匹配的录制响应
+    fn play(&self, request: &HttpRequest) -> CurseForgeResult<HttpResponse> {
+        let mut interactions = self.lock_interactions();
+        let matched: Vec<usize> = (0..interactions.len())
+            .filter(|&i| interactions[i].request.matches(request))
+            .collect();
+        match matched.as_slice() {
+            [] => Err(Error::Transport(
+                format!(
+                    "no recorded interaction for {} {} in cassette {}",
+                    request.method,
+                    request.url,
+                    self.path.display()
+                )
+                .into(),
+            )),
+            [only] => interactions[*only].response.to_response(),
+            [first, ..] => interactions.remove(*first).response.to_response(),
+        }
+    }
+
+    /// 记录一次请求和响应，并写入磁带文件
+    fn remember(&self, request: RecordedRequest, response: &HttpResponse) -> CurseForgeResult<()> {
+        let mut interactions = self.lock_interactions();
+        interactions.push(Interaction {
+            request,
+            response: RecordedResponse::new(response),
+        });
+        self.save(&interactions)
+    }
+
+    /// 将已录制的请求写入磁带文件
+    fn save(&self, interactions: &[Interaction]) -> CurseForgeResult<()> {
+        let cassette = Cassette {
+            interactions: interactions.to_vec(),
+        };
+        let content =
+            serde_json::to_vec_pretty(&cassette).map_err(|e| Error::Transport(Box::new(e)))?;
+        let temp_path = self.path.with_extension("tmp");
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| Error::Transport(Box::new(e)))?;
+        }
+        std::fs::write(&temp_path, content)
+            .and_then(|_| std::fs::rename(&temp_path, &self.path))
+            .map_err(|e| Error::Transport(Box::new(e)))
+    }
+}
+
+impl CassetteTransport {
+    /// 回放模式，读取`path`中录制的请求
+    pub fn replay(path: impl AsRef<Path>) -> CurseForgeResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let content = std::fs::read(&path).map_err(|e| {
+            Error::Config(format!("cannot read cassette {}: {}", path.display(), e))
+        })?;
+        let cassette: Cassette = serde_json::from_slice(&content)
+            .map_err(|e| Error::Config(format!("invalid cassette {}: {}", path.display(), e)))?;
+        Ok(Self {
+            path,
+            inner: None,
+            interactions: Arc::new(Mutex::new(cassette.interactions)),
+        })
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<T: Transport> Transport for CassetteTransport<T> {
+    fn send(&self, request: HttpRequest) -> CurseForgeResult<HttpResponse> {
+        let Some(inner) = &self.inner else {
+            return self.play(&request);
+        };
+        let recorded = RecordedRequest::new(&request);
+        let response = inner.send(request)?;
+        self.remember(recorded, &response)?;
+        Ok(response)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T: AsyncTransport> AsyncTransport for CassetteTransport<T> {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let Some(inner) = &self.inner else {
+                return self.play(&request);
+            };
+            let recorded = RecordedRequest::new(&request);
+            let response = inner.send(request).await?;
+            self.remember(recorded, &response)?;
+            Ok(response)
+        })
+    }
+}
+
+/// 回放模式没有内部传输层
+#[cfg(feature = "blocking")]
+impl Transport for Infallible {
+    fn send(&self, _: HttpRequest) -> CurseForgeResult<HttpResponse> {
+        match *self {}
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncTransport for Infallible {
+    fn send(&self, _: HttpRequest) -> TransportFuture<'_> {
+        match *self {}
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod tests {
+    use super::*;
+    use crate::curseforge::api::GamesApi;
+    use crate::curseforge::client::testing::{MINECRAFT, serve};
+    use crate::curseforge::client::{Client, FixtureTransport};
+    use reqwest::Method;
+
+    fn request(method: Method, url: &str, api_key: &str) -> HttpRequest {
+        let mut request = HttpRequest::new(method, Url::parse(url).unwrap());
+        request
+            .headers
+            .insert("x-api-key", HeaderValue::from_str(api_key).unwrap());
+        request
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let path = std::env::temp_dir().join("curseforge-api-test-cassette.json");
+        let fixtures = FixtureTransport::new()
+            .json("/v1/mods/238222", 200, r#"{"data":{"id":238222}}"#)
+            .json("/v1/mods/1", 404, "");
+
+        let recorder = CassetteTransport::record(&path, fixtures);
+        let url = "https://api.curseforge.com/v1/mods/238222";
+        let response = Transport::send(&recorder, request(Method::GET, url, "secret")).unwrap();
+        assert_eq!(response.status, StatusCode::OK);
+        let url = "https://api.curseforge.com/v1/mods/1";
+        Transport::send(&recorder, request(Method::GET, url, "secret")).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("secret"));
+
+        let player = CassetteTransport::replay(&path).unwrap();
+        let url = "http://localhost/v1/mods/238222";
+        let response = Transport::send(&player, request(Method::GET, url, "other")).unwrap();
+        assert_eq!(response.body, br#"{"data":{"id":238222}}"#);
+        let url = "http://localhost/v1/mods/1";
+        let response = Transport::send(&player, request(Method::GET, url, "other")).unwrap();
+        assert_eq!(response.status, StatusCode::NOT_FOUND);
+
+        let url = "http://localhost/v1/mods/238222?gameId=432";
+        let result = Transport::send(&player, request(Method::GET, url, "other"));
+        assert!(
+            matches!(result, Err(Error::Transport(e)) if e.to_string().contains("no recorded"))
+        );
+        let url = "http://localhost/v1/mods/238222";
+        assert!(Transport::send(&player, request(Method::POST, url, "other")).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_record_with_http_options() {
+        let path = std::env::temp_dir().join("curseforge-api-test-cassette-wrapped.json");
+        let (base_url, requests) = serve(vec![(200, MINECRAFT)]);
+        let recorder_path = path.clone();
+        let client = Client::builder()
+            .base_url(&base_url)
+            .api_key("test")
+            .default_header("x-client", "recorder")
+            .wrap_transport(move |inner| CassetteTransport::record(recorder_path, inner))
+            .build()
+            .unwrap();
+        assert_eq!(client.get_game(432).unwrap().data.id, 432);
+        let requests = requests.lock().unwrap().clone();
+        assert!(
+            requests[0]
+                .to_lowercase()
+                .contains("x-client: recorder\r\n")
+        );
+
+        let client = Client::builder()
+            .base_url(&base_url)
+            .api_key("test")
+            .transport(CassetteTransport::replay(&path).unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(client.get_game(432).unwrap().data.name, "Minecraft");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_replay_missing_cassette() {
+        let result = CassetteTransport::replay("/nonexistent/cassette.json");
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
+}
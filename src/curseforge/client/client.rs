@@ -1,16 +1,50 @@
-use std::time::Duration;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Instant;
 
 use crate::curseforge::error::CurseForgeResult;
+#[cfg(feature = "async")]
+use crate::curseforge::error::Error;
 use crate::curseforge::schemas::*;
 
-use super::super::api::{CategoriesApi, FilesApi, GamesApi, ModsApi};
-use super::ClientBuilder;
-use reqwest::blocking::Client as HttpClient;
+use super::super::api::{
+    CategoriesApi, FilesApi, FingerprintsApi, GamesApi, MinecraftApi, ModsApi,
+};
+use super::batch;
+use super::cache::{self, Plan, ResponseCache};
+use super::endpoints::{self, Endpoint};
+use super::middleware::MiddlewareChain;
+use super::rate_limit::RateLimiter;
+use super::retry::Retry;
+use super::transport::{
+    HttpRequest, HttpResponse, ReqwestTransport, Transport, complete, request_span,
+};
+use super::{ClientBuilder, ClientConfig, RetryPolicy};
+use serde::de::DeserializeOwned;
 
+#[derive(Clone)]
 pub struct Client {
     base_url: String,
     api_key: String,
-    http_client: HttpClient,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    cache: Option<Arc<ResponseCache>>,
+    middlewares: MiddlewareChain,
+    transport: Arc<dyn Transport>,
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("base_url", &self.base_url)
+            .field("api_key", &"<redacted>")
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("cache", &self.cache)
+            .field("middlewares", &self.middlewares.len())
+            .finish_non_exhaustive()
+    }
 }
 
 impl Client {
@@ -18,18 +52,105 @@ impl Client {
         ClientBuilder::new()
     }
 
-    pub fn new(base_url: &str, api_key: &str) -> Self {
-        let mut http_client_builder = HttpClient::builder();
-        http_client_builder = http_client_builder.connect_timeout(Duration::from_millis(3000));
-        let http_client = http_client_builder.build().unwrap();
-        Self {
-            base_url: String::from(base_url),
-            api_key: String::from(api_key),
-            http_client,
+    pub fn new(base_url: &str, api_key: &str) -> CurseForgeResult<Self> {
+        Self::from_config(ClientConfig::new(base_url, api_key))
+    }
+
+    pub(crate) fn from_config(config: ClientConfig) -> CurseForgeResult<Self> {
+        #[cfg(feature = "async")]
+        if config.async_transport.is_some() {
+            return Err(Error::Config(String::from(
+                "async transports are only supported by AsyncClient",
+            )));
+        }
+        let transport = match config.transport {
+            Some(factory) => factory(&config.http)?,
+            None => Arc::new(ReqwestTransport::with_options(&config.http)?),
+        };
+        Ok(Self {
+            base_url: config.base_url,
+            api_key: config.api_key,
+            retry_policy: config.retry_policy,
+            rate_limiter: config.rate_limiter,
+            cache: config.cache,
+            middlewares: config.middlewares,
+            transport,
+        })
+    }
+
+    /// 发送请求并解析响应，记录请求日志
+    fn call<T>(&self, endpoint: Endpoint) -> CurseForgeResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        let request = endpoint.request(&self.base_url, &self.api_key)?;
+        let idempotent = endpoint.idempotent;
+        let span = request_span(&endpoint.path, &request);
+        let _enter = span.enter();
+        let started = Instant::now();
+        complete(started, self.execute(request, idempotent))
+    }
+
+    /// 发送请求并返回成功响应的响应体，GET请求优先使用缓存，离线模式下不发送其他请求
+    fn execute(&self, mut request: HttpRequest, idempotent: bool) -> CurseForgeResult<Vec<u8>> {
+        match cache::plan(self.cache.as_ref(), &mut request)? {
+            Plan::Cached(body) => Ok(body),
+            Plan::Send(pending) => pending.finish(self.send(request, idempotent)?),
+        }
+    }
+
+    /// 发送请求，按重试策略重试失败的幂等请求，返回成功或304响应
+    fn send(&self, request: HttpRequest, idempotent: bool) -> CurseForgeResult<HttpResponse> {
+        let mut attempt = 1;
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                std::thread::sleep(rate_limiter.reserve());
+            }
+
+            let mut current = request.clone();
+            self.middlewares.before(&mut current)?;
+            let started = Instant::now();
+            let result = self.transport.send(current.clone());
+            self.middlewares.after(&current, &result, started.elapsed());
+
+            match self.retry_policy.next(idempotent, attempt, result) {
+                Retry::Done(result) => return result,
+                Retry::After(backoff) => std::thread::sleep(backoff),
+            }
+            attempt += 1;
         }
     }
 }
 
+impl GamesApi for Client {
+    fn get_games(
+        &self,
+        index: Option<u32>,
+        page_size: Option<u32>,
+    ) -> CurseForgeResult<PaginationResponse<Game>> {
+        self.call(endpoints::get_games(index, page_size)?)
+    }
+
+    fn get_game(&self, game_id: u32) -> CurseForgeResult<DataResponse<Game>> {
+        self.call(endpoints::get_game(game_id))
+    }
+
+    fn get_versions(&self, game_id: u32) -> CurseForgeResult<ListResponse<GameVersionsByType>> {
+        self.call(endpoints::get_versions(game_id))
+    }
+
+    fn get_versions_v2(
+        &self,
+        game_id: u32,
+    ) -> CurseForgeResult<ListResponse<GameVersionsByTypeV2>> {
+        self.call(endpoints::get_versions_v2(game_id))
+    }
+
+    fn get_version_types(&self, game_id: u32) -> CurseForgeResult<ListResponse<GameVersionType>> {
+        self.call(endpoints::get_version_types(game_id))
+    }
+}
+
 impl CategoriesApi for Client {
     fn get_categories(
         &self,
@@ -37,19 +158,156 @@ impl CategoriesApi for Client {
         class_id: Option<u32>,
         classes_only: Option<bool>,
     ) -> CurseForgeResult<ListResponse<Category>> {
-        let mut params: Vec<String> = vec![];
-        params.push(format!("gameId={}", game_id));
-        if let Some(class_id) = class_id {
-            params.push(format!("classId={}", class_id));
+        self.call(endpoints::get_categories(game_id, class_id, classes_only)?)
+    }
+}
+
+impl ModsApi for Client {
+    fn search_mods(
+        &self,
+        params: SearchModsParameters,
+    ) -> CurseForgeResult<PaginationResponse<Mod>> {
+        self.call(endpoints::search_mods(&params)?)
+    }
+
+    fn get_mod(&self, mod_id: u32) -> CurseForgeResult<DataResponse<Mod>> {
+        self.call(endpoints::get_mod(mod_id))
+    }
+
+    fn get_mod_description(&self, mod_id: u32) -> CurseForgeResult<DataResponse<String>> {
+        self.call(endpoints::get_mod_description(mod_id))
+    }
+
+    fn get_mods(
+        &self,
+        mod_ids: &[u32],
+        filter_pc_only: Option<bool>,
+    ) -> CurseForgeResult<BatchResponse<Vec<Mod>>> {
+        let mut mods = vec![];
+        for endpoint in endpoints::get_mods(mod_ids, filter_pc_only)? {
+            let response: ListResponse<Mod> = self.call(endpoint)?;
+            mods.extend(response.data);
         }
-        if let Some(classes_only) = classes_only {
-            params.push(format!("classesOnly={}", classes_only));
+        Ok(batch::in_request_order(mod_ids, mods, |m| m.id))
+    }
+
+    fn get_featured_mods(
+        &self,
+        game_id: u32,
+        excluded_mod_ids: &[u32],
+        game_version_type_id: Option<u32>,
+    ) -> CurseForgeResult<DataResponse<FeaturedModsResponse>> {
+        let endpoint =
+            endpoints::get_featured_mods(game_id, excluded_mod_ids, game_version_type_id)?;
+        self.call(endpoint)
+    }
+}
+
+impl FilesApi for Client {
+    fn get_mod_file(&self, mod_id: u32, file_id: u32) -> CurseForgeResult<DataResponse<File>> {
+        self.call(endpoints::get_mod_file(mod_id, file_id))
+    }
+
+    fn get_mod_file_changelog(
+        &self,
+        mod_id: u32,
+        file_id: u32,
+    ) -> CurseForgeResult<DataResponse<String>> {
+        self.call(endpoints::get_mod_file_changelog(mod_id, file_id))
+    }
+
+    fn get_mod_file_download_url(
+        &self,
+        mod_id: u32,
+        file_id: u32,
+    ) -> CurseForgeResult<DataResponse<Option<String>>> {
+        self.call(endpoints::get_mod_file_download_url(mod_id, file_id))
+    }
+
+    fn get_mod_files(
+        &self,
+        mod_id: u32,
+        options: GetModFilesParameters,
+    ) -> CurseForgeResult<PaginationResponse<File>> {
+        self.call(endpoints::get_mod_files(mod_id, &options)?)
+    }
+
+    fn get_files(&self, file_ids: &[u32]) -> CurseForgeResult<BatchResponse<HashMap<u32, File>>> {
+        let mut files = vec![];
+        for endpoint in endpoints::get_files(file_ids)? {
+            let response: ListResponse<File> = self.call(endpoint)?;
+            files.extend(response.data);
         }
-        let query = format!("?{}", params.join("&"));
-        let url = format!("{}/v1/categories{}", self.base_url, query);
-        let resp = self.http_client.get(url).send().unwrap();
-        let resp_body: ListResponse<Category> = resp.json().unwrap();
-        Ok(resp_body)
+        Ok(batch::by_id(file_ids, files, |f| f.id))
+    }
+}
+
+impl FingerprintsApi for Client {
+    fn get_fingerprint_matches(
+        &self,
+        fingerprints: &[u32],
+    ) -> CurseForgeResult<DataResponse<FingerprintsMatchesResult>> {
+        self.call(endpoints::get_fingerprint_matches(None, fingerprints)?)
+    }
+
+    fn get_fingerprint_matches_by_game(
+        &self,
+        game_id: u32,
+        fingerprints: &[u32],
+    ) -> CurseForgeResult<DataResponse<FingerprintsMatchesResult>> {
+        self.call(endpoints::get_fingerprint_matches(
+            Some(game_id),
+            fingerprints,
+        )?)
+    }
+
+    fn get_fingerprint_fuzzy_matches(
+        &self,
+        game_id: u32,
+        fingerprints: &[FolderFingerprint],
+    ) -> CurseForgeResult<DataResponse<FingerprintFuzzyMatchResult>> {
+        let endpoint = endpoints::get_fingerprint_fuzzy_matches(game_id, false, fingerprints)?;
+        self.call(endpoint)
+    }
+
+    fn get_fingerprint_fuzzy_matches_by_game(
+        &self,
+        game_id: u32,
+        fingerprints: &[FolderFingerprint],
+    ) -> CurseForgeResult<DataResponse<FingerprintFuzzyMatchResult>> {
+        let endpoint = endpoints::get_fingerprint_fuzzy_matches(game_id, true, fingerprints)?;
+        self.call(endpoint)
+    }
+}
+
+impl MinecraftApi for Client {
+    fn get_minecraft_versions(
+        &self,
+        sort_descending: Option<bool>,
+    ) -> CurseForgeResult<ListResponse<MinecraftGameVersion>> {
+        self.call(endpoints::get_minecraft_versions(sort_descending)?)
+    }
+
+    fn get_minecraft_version(
+        &self,
+        game_version: &str,
+    ) -> CurseForgeResult<DataResponse<MinecraftGameVersion>> {
+        self.call(endpoints::get_minecraft_version(game_version)?)
+    }
+
+    fn get_minecraft_mod_loaders(
+        &self,
+        version: Option<&str>,
+        include_all: Option<bool>,
+    ) -> CurseForgeResult<ListResponse<MinecraftModLoaderIndex>> {
+        self.call(endpoints::get_minecraft_mod_loaders(version, include_all)?)
+    }
+
+    fn get_minecraft_mod_loader(
+        &self,
+        mod_loader_name: &str,
+    ) -> CurseForgeResult<DataResponse<MinecraftModLoaderVersion>> {
+        self.call(endpoints::get_minecraft_mod_loader(mod_loader_name)?)
     }
 }
 
@@ -57,8 +315,440 @@ impl CategoriesApi for Client {
 mod tests {
     use super::*;
 
+    use crate::curseforge::client::testing::{Counter, MINECRAFT, serve};
+    use crate::curseforge::client::{CacheOptions, FaultTransport, FixtureTransport};
+    use crate::curseforge::error::Error;
+    use reqwest::{Method, StatusCode};
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+
+    fn client(transport: &FixtureTransport) -> ClientBuilder {
+        Client::builder()
+            .base_url("http://localhost")
+            .api_key("test")
+            .transport(transport.clone())
+    }
+
     #[test]
     fn test_build() {
-        let client = Client::builder().build();
+        let client = Client::builder().api_key("test").build();
+        assert!(client.is_ok());
+    }
+
+    #[test]
+    fn test_get_game() {
+        let transport = FixtureTransport::new().json("/v1/games/432", 200, MINECRAFT);
+        let client = client(&transport).build().unwrap();
+
+        let minecraft = client.get_game(432).unwrap().data;
+        assert_eq!(minecraft.name, "Minecraft");
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].headers.get("x-api-key").unwrap(), "test");
+    }
+
+    #[test]
+    fn test_get_games_pagination() {
+        let body = r#"{"data": [{
+            "id": 432,
+            "name": "Minecraft",
+            "slug": "minecraft",
+            "dateModified": "2024-08-29T09:54:14.45Z",
+            "assets": {"iconUrl": "", "tileUrl": "", "coverUrl": ""},
+            "status": 6,
+            "apiStatus": 2
+        }], "pagination": {"index": 0, "pageSize": 50, "resultCount": 1, "totalCount": 1}}"#;
+        let transport = FixtureTransport::new().json("/v1/games", 200, body);
+        let client = client(&transport).build().unwrap();
+
+        let games = client.get_games(None, None).unwrap();
+        assert_eq!(games.data[0].name, "Minecraft");
+        assert_eq!(games.pagination.page_size, 50);
+        assert_eq!(games.pagination.result_count, 1);
+        assert_eq!(games.pagination.total_count, 1);
+    }
+
+    #[test]
+    fn test_middleware() {
+        let transport = FixtureTransport::new().json("/v1/games/432", 503, "").json(
+            "/v1/games/432",
+            200,
+            MINECRAFT,
+        );
+        let counter = Counter::default();
+        let responses = counter.responses.clone();
+        let policy = RetryPolicy::new().initial_backoff(Duration::ZERO);
+        let client = client(&transport)
+            .retry_policy(policy)
+            .middleware(counter)
+            .build()
+            .unwrap();
+
+        assert_eq!(client.get_game(432).unwrap().data.id, 432);
+        assert_eq!(responses.load(Ordering::SeqCst), 2);
+        for request in transport.requests() {
+            assert_eq!(request.headers.get("x-request-id").unwrap(), "1");
+        }
+    }
+
+    #[test]
+    fn test_retry_dropped_connection() {
+        let (base_url, requests) = serve(vec![(0, ""), (200, MINECRAFT)]);
+        let client = Client::builder()
+            .base_url(&base_url)
+            .api_key("test")
+            .retry_policy(RetryPolicy::new().initial_backoff(Duration::ZERO))
+            .build()
+            .unwrap();
+
+        assert_eq!(client.get_game(432).unwrap().data.id, 432);
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_wrap_transport() {
+        let (base_url, requests) = serve(vec![(200, MINECRAFT)]);
+        let client = Client::builder()
+            .base_url(&base_url)
+            .api_key("test")
+            .wrap_transport(|inner| FaultTransport::new(inner, 42))
+            .user_agent("wrapped/1.0")
+            .build()
+            .unwrap();
+
+        assert_eq!(client.get_game(432).unwrap().data.id, 432);
+        let requests = requests.lock().unwrap().clone();
+        assert!(
+            requests[0]
+                .to_lowercase()
+                .contains("user-agent: wrapped/1.0\r\n")
+        );
+    }
+
+    fn mod_json(id: u32) -> String {
+        format!(
+            r#"{{"id": {id}, "gameId": 432, "name": "Mod {id}", "slug": "mod-{id}",
+            "links": {{"websiteUrl": "https://www.curseforge.com/minecraft/mc-mods/mod-{id}"}},
+            "summary": "", "status": 4, "downloadCount": 0, "primaryCategoryId": 423,
+            "isFeatured": false, "categories": [], "classId": 6, "authors": [], "mainFileId": 1,
+            "logo": {{"id": 1, "modId": {id}, "title": "", "description": "",
+                "thumbnailUrl": "", "url": ""}},
+            "screenshots": [], "latestFiles": [], "latestFilesIndexes": [],
+            "latestEarlyAccessFilesIndexes": [], "dateCreated": "", "dateModified": "",
+            "dateReleased": "", "allowModDistribution": true, "gamePopularityRank": {id},
+            "isAvailable": true, "thumbsUpCount": 0}}"#
+        )
+    }
+
+    #[test]
+    fn test_get_mods_chunked() {
+        let list = |ids: &[u32]| {
+            let mods: Vec<String> = ids.iter().map(|id| mod_json(*id)).collect();
+            HttpResponse::new(
+                StatusCode::OK,
+                format!(r#"{{"data": [{}]}}"#, mods.join(",")),
+            )
+        };
+        let transport = FixtureTransport::new()
+            .response(Method::POST, "/v1/mods", list(&[150]))
+            .response(Method::POST, "/v1/mods", list(&[2, 1]));
+        let client = client(&transport).build().unwrap();
+
+        let mut mod_ids: Vec<u32> = (1..=150).rev().collect();
+        mod_ids.push(1);
+        let response = client.get_mods(&mod_ids, Some(true)).unwrap();
+        let ids: Vec<u32> = response.data.iter().map(|m| m.id).collect();
+        assert_eq!(ids, [150, 2, 1]);
+        assert_eq!(response.missing_ids.len(), 147);
+        assert_eq!(response.missing_ids[0], 149);
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        let body: serde_json::Value =
+            serde_json::from_slice(requests[0].body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["modIds"].as_array().unwrap().len(), 100);
+        assert_eq!(body["filterPcOnly"], true);
+        assert_eq!(
+            requests[0].headers.get("content-type").unwrap(),
+            "application/json"
+        );
+
+        assert!(client.get_mods(&[], None).unwrap().data.is_empty());
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[test]
+    fn test_retry_batch_requests() {
+        let list = HttpResponse::new(StatusCode::OK, format!(r#"{{"data": [{}]}}"#, mod_json(1)));
+        let transport = FixtureTransport::new()
+            .response(
+                Method::POST,
+                "/v1/mods",
+                HttpResponse::new(StatusCode::SERVICE_UNAVAILABLE, ""),
+            )
+            .response(Method::POST, "/v1/mods", list);
+        let policy = RetryPolicy::new().initial_backoff(Duration::ZERO);
+        let client = client(&transport).retry_policy(policy).build().unwrap();
+
+        let response = client.get_mods(&[1], None).unwrap();
+        assert_eq!(response.data[0].id, 1);
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[test]
+    fn test_get_description_and_changelog() {
+        let transport = FixtureTransport::new()
+            .json(
+                "/v1/mods/238222/description",
+                200,
+                r#"{"data": "<p>JEI</p>"}"#,
+            )
+            .json(
+                "/v1/mods/238222/files/5101366/changelog",
+                200,
+                r#"{"data": "<ul><li>Fix</li></ul>"}"#,
+            );
+        let client = client(&transport).build().unwrap();
+
+        let description = client.get_mod_description(238222).unwrap().data;
+        assert_eq!(description, "<p>JEI</p>");
+        let changelog = client.get_mod_file_changelog(238222, 5101366).unwrap().data;
+        assert_eq!(changelog, "<ul><li>Fix</li></ul>");
+    }
+
+    #[test]
+    fn test_get_download_url() {
+        let transport = FixtureTransport::new()
+            .json(
+                "/v1/mods/238222/files/1/download-url",
+                200,
+                r#"{"data": "https://edge.forgecdn.net/files/0/1/jei.jar"}"#,
+            )
+            .json(
+                "/v1/mods/238222/files/2/download-url",
+                200,
+                r#"{"data": null}"#,
+            );
+        let client = client(&transport).build().unwrap();
+
+        let url = client.get_mod_file_download_url(238222, 1).unwrap().data;
+        assert_eq!(
+            url.as_deref(),
+            Some("https://edge.forgecdn.net/files/0/1/jei.jar")
+        );
+        let url = client.get_mod_file_download_url(238222, 2).unwrap().data;
+        assert_eq!(url, None);
+    }
+
+    #[test]
+    fn test_fingerprint_matches() {
+        let empty = r#"{"data": {"isCacheBuilt": true, "exactMatches": [],
+            "exactFingerprints": [], "partialMatches": [], "installedFingerprints": [],
+            "unmatchedFingerprints": [1396938946]}}"#;
+        let transport = FixtureTransport::new()
+            .response(
+                Method::POST,
+                "/v1/fingerprints/432",
+                HttpResponse::new(StatusCode::OK, empty),
+            )
+            .response(
+                Method::POST,
+                "/v1/fingerprints/fuzzy",
+                HttpResponse::new(StatusCode::OK, r#"{"data": {"fuzzyMatches": []}}"#),
+            );
+        let client = client(&transport).build().unwrap();
+
+        let result = client
+            .get_fingerprint_matches_by_game(432, &[1396938946])
+            .unwrap()
+            .data;
+        assert_eq!(result.unmatched_fingerprints, [1396938946]);
+        let folders = [FolderFingerprint::new("jei", vec![1, 2])];
+        let result = client.get_fingerprint_fuzzy_matches(432, &folders).unwrap();
+        assert!(result.data.fuzzy_matches.is_empty());
+
+        let requests = transport.requests();
+        assert_eq!(
+            requests[0].body.as_deref(),
+            Some(&br#"{"fingerprints":[1396938946]}"#[..])
+        );
+        assert_eq!(
+            requests[1].body.as_deref(),
+            Some(
+                &br#"{"gameId":432,"fingerprints":[{"foldername":"jei","fingerprints":[1,2]}]}"#[..]
+            )
+        );
+    }
+
+    #[test]
+    fn test_get_featured_mods() {
+        let body = format!(
+            r#"{{"data": {{"featured": [{}], "popular": [{}, {}], "recentlyUpdated": []}}}}"#,
+            mod_json(1),
+            mod_json(2),
+            mod_json(3)
+        );
+        let transport = FixtureTransport::new().response(
+            Method::POST,
+            "/v1/mods/featured",
+            HttpResponse::new(StatusCode::OK, body),
+        );
+        let client = client(&transport).build().unwrap();
+
+        let response = client.get_featured_mods(432, &[4], None).unwrap().data;
+        assert_eq!(response.featured[0].id, 1);
+        assert_eq!(response.popular.len(), 2);
+        assert!(response.recently_updated.is_empty());
+
+        let requests = transport.requests();
+        let body = requests[0].body.as_deref().unwrap();
+        assert_eq!(body, br#"{"gameId":432,"excludedModIds":[4]}"#);
+    }
+
+    #[test]
+    fn test_get_files_by_id() {
+        let file = |id: u32| {
+            format!(
+                r#"{{"id": {id}, "gameId": 432, "modId": 238222, "isAvailable": true,
+                "displayName": "", "fileName": "jei-{id}.jar", "releaseType": 1,
+                "fileStatus": 4, "hashes": [], "fileDate": "", "fileLength": 0,
+                "downloadCount": 0, "downloadUrl": "", "gameVersions": [],
+                "sortableGameVersions": [], "dependencies": [], "fileFingerprint": {id},
+                "modules": []}}"#
+            )
+        };
+        let body = format!(r#"{{"data": [{}, {}]}}"#, file(5), file(4));
+        let transport = FixtureTransport::new().response(
+            Method::POST,
+            "/v1/mods/files",
+            HttpResponse::new(StatusCode::OK, body),
+        );
+        let client = client(&transport).build().unwrap();
+
+        let response = client.get_files(&[4, 5, 6]).unwrap();
+        assert_eq!(response.data[&4].id, 4);
+        assert_eq!(response.data[&5].id, 5);
+        assert_eq!(response.missing_ids, [6]);
+
+        let requests = transport.requests();
+        assert_eq!(
+            requests[0].body.as_deref(),
+            Some(&br#"{"fileIds":[4,5,6]}"#[..])
+        );
+    }
+
+    #[test]
+    fn test_status_error() {
+        let transport = FixtureTransport::new().json("/v1/games/1", 404, "not found");
+        let client = client(&transport).build().unwrap();
+
+        match client.get_game(1) {
+            Err(Error::Status { status, body }) => {
+                assert_eq!(status, 404);
+                assert_eq!(body, "not found");
+            }
+            result => panic!("unexpected result: {:?}", result.map(|r| r.data.id)),
+        }
+    }
+
+    #[test]
+    fn test_retry() {
+        let transport = FixtureTransport::new()
+            .json("/v1/games/432", 503, "")
+            .json("/v1/games/432", 429, "")
+            .json("/v1/games/432", 200, MINECRAFT);
+        let retry_policy = RetryPolicy::new().initial_backoff(Duration::from_millis(1));
+        let client = client(&transport)
+            .retry_policy(retry_policy)
+            .build()
+            .unwrap();
+
+        assert_eq!(client.get_game(432).unwrap().data.id, 432);
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[test]
+    fn test_retry_exhausted() {
+        let transport = FixtureTransport::new().json("/v1/games/432", 503, "");
+        let retry_policy = RetryPolicy::new()
+            .max_attempts(2)
+            .initial_backoff(Duration::from_millis(1));
+        let client = client(&transport)
+            .retry_policy(retry_policy)
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            client.get_game(432),
+            Err(Error::Status { status: 503, .. })
+        ));
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[test]
+    fn test_cache_revalidate() {
+        let mut fresh = HttpResponse::new(StatusCode::OK, MINECRAFT);
+        fresh.headers.insert("etag", "\"v1\"".parse().unwrap());
+        let transport = FixtureTransport::new()
+            .response(Method::GET, "/v1/games/432", fresh)
+            .json("/v1/games/432", 304, "");
+        let cache = CacheOptions::new().ttl("/v1/games", Duration::ZERO);
+        let client = client(&transport).cache(cache).build().unwrap();
+
+        assert_eq!(client.get_game(432).unwrap().data.id, 432);
+        assert_eq!(client.get_game(432).unwrap().data.id, 432);
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].headers.get("if-none-match").is_none());
+        assert_eq!(requests[1].headers.get("if-none-match").unwrap(), "\"v1\"");
+    }
+
+    #[test]
+    fn test_offline_skips_post() {
+        let transport = FixtureTransport::new().response(
+            Method::POST,
+            "/v1/mods",
+            HttpResponse::new(StatusCode::OK, r#"{"data": []}"#),
+        );
+        let cache = CacheOptions::new().offline(true);
+        let client = client(&transport).cache(cache).build().unwrap();
+
+        assert!(matches!(
+            client.get_mods(&[1], None),
+            Err(Error::Offline(_))
+        ));
+        assert!(transport.requests().is_empty());
+    }
+
+    #[test]
+    fn test_minecraft_mod_loaders() {
+        let transport = FixtureTransport::new().json(
+            "/v1/minecraft/modloader",
+            200,
+            r#"{"data": [{"name": "forge-47.2.0", "gameVersion": "1.20.1", "latest": false,
+                "recommended": true, "dateModified": "2023-09-12T22:02:26.837Z", "type": 1}]}"#,
+        );
+        let client = client(&transport).build().unwrap();
+
+        let mod_loaders = client
+            .get_minecraft_mod_loaders(Some("1.20.1"), Some(true))
+            .unwrap()
+            .data;
+        assert_eq!(mod_loaders[0].name, "forge-47.2.0");
+        assert_eq!(mod_loaders[0].mod_loader_type, ModLoaderType::Forge);
+        assert_eq!(
+            transport.requests()[0].url.query(),
+            Some("version=1.20.1&includeAll=true")
+        );
+
+        let result = client.get_minecraft_mod_loader("forge/../../v1/mods?x#");
+        assert!(result.is_err());
+        assert_eq!(
+            transport.requests()[1].url.path(),
+            "/v1/minecraft/modloader/forge%2F..%2F..%2Fv1%2Fmods%3Fx%23"
+        );
+        assert!(transport.requests()[1].url.query().is_none());
     }
 }
@@ -0,0 +1,346 @@
+use reqwest::{Method, Url};
+use serde::Serialize;
+
+use super::batch;
+use super::transport::{HttpRequest, api_request};
+use crate::curseforge::error::{CurseForgeResult, Error};
+use crate::curseforge::schemas::*;
+
+/// 一次API调用的方法、路径、查询参数和请求体，由阻塞客户端和异步客户端共用
+#[derive(Debug, Clone)]
+pub(crate) struct Endpoint {
+    pub(crate) method: Method,
+    pub(crate) path: String,
+    query: String,
+    body: Option<Vec<u8>>,
+    /// 重复发送是否安全，决定失败后能否重试
+    pub(crate) idempotent: bool,
+}
+
+impl Endpoint {
+    fn get(path: String) -> Self {
+        Self {
+            method: Method::GET,
+            path,
+            query: String::new(),
+            body: None,
+            idempotent: true,
+        }
+    }
+
+    fn get_with<Q>(path: String, query: &Q) -> CurseForgeResult<Self>
+    where
+        Q: Serialize + ?Sized,
+    {
+        let query = serde_urlencoded::to_string(query)
+            .map_err(|e| Error::Config(format!("invalid query for {}: {}", path, e)))?;
+        Ok(Self {
+            query,
+            ..Self::get(path)
+        })
+    }
+
+    fn post<B>(path: String, body: &B) -> CurseForgeResult<Self>
+    where
+        B: Serialize + ?Sized,
+    {
+        let body = serde_json::to_vec(body)
+            .map_err(|e| Error::Config(format!("cannot serialize request body: {}", e)))?;
+        Ok(Self {
+            method: Method::POST,
+            path,
+            query: String::new(),
+            body: Some(body),
+            idempotent: false,
+        })
+    }
+
+    /// 标记只读取数据的POST请求，和GET请求一样可以重试
+    fn idempotent(self) -> Self {
+        Self {
+            idempotent: true,
+            ..self
+        }
+    }
+
+    /// 拼接`base_url`、路径和查询参数
+    pub(crate) fn url(&self, base_url: &str) -> CurseForgeResult<Url> {
+        let url = format!("{}{}", base_url, self.path);
+        let mut url = Url::parse(&url)
+            .map_err(|e| Error::Config(format!("invalid base_url `{}`: {}", base_url, e)))?;
+        if !self.query.is_empty() {
+            url.set_query(Some(&self.query));
+        }
+        Ok(url)
+    }
+
+    /// 构造带有API Key的请求
+    pub(crate) fn request(&self, base_url: &str, api_key: &str) -> CurseForgeResult<HttpRequest> {
+        let request = api_request(self.method.clone(), self.url(base_url)?, api_key)?;
+        match &self.body {
+            Some(body) => Ok(request.json(body.clone())),
+            None => Ok(request),
+        }
+    }
+}
+
+/// 将调用方提供的字符串编码为单个路径段，避免其中的`/`、`?`、`#`改变路径或查询参数
+fn path_segment(segment: &str) -> CurseForgeResult<String> {
+    // `.`和`..`即使编码后也会被URL解析为相对路径
+    if segment.is_empty() || segment == "." || segment == ".." {
+        return Err(Error::Config(format!("invalid path segment `{}`", segment)));
+    }
+    let mut url = Url::parse("http://localhost/").expect("static URL is valid");
+    url.path_segments_mut()
+        .expect("http URL has a path")
+        .pop_if_empty()
+        .push(segment);
+    Ok(url.path()[1..].to_string())
+}
+
+pub(crate) fn get_games(index: Option<u32>, page_size: Option<u32>) -> CurseForgeResult<Endpoint> {
+    let mut params: Vec<(&str, String)> = vec![];
+    if let Some(index) = index {
+        params.push(("index", index.to_string()));
+    }
+    if let Some(page_size) = page_size {
+        params.push(("pageSize", page_size.to_string()));
+    }
+    Endpoint::get_with(String::from("/v1/games"), &params)
+}
+
+pub(crate) fn get_game(game_id: u32) -> Endpoint {
+    Endpoint::get(format!("/v1/games/{}", game_id))
+}
+
+pub(crate) fn get_versions(game_id: u32) -> Endpoint {
+    Endpoint::get(format!("/v1/games/{}/versions", game_id))
+}
+
+pub(crate) fn get_versions_v2(game_id: u32) -> Endpoint {
+    Endpoint::get(format!("/v2/games/{}/versions", game_id))
+}
+
+pub(crate) fn get_version_types(game_id: u32) -> Endpoint {
+    Endpoint::get(format!("/v1/games/{}/version-types", game_id))
+}
+
+pub(crate) fn get_categories(
+    game_id: u32,
+    class_id: Option<u32>,
+    classes_only: Option<bool>,
+) -> CurseForgeResult<Endpoint> {
+    let mut params: Vec<(&str, String)> = vec![];
+    params.push(("gameId", game_id.to_string()));
+    if let Some(class_id) = class_id {
+        params.push(("classId", class_id.to_string()));
+    }
+    if let Some(classes_only) = classes_only {
+        params.push(("classesOnly", classes_only.to_string()));
+    }
+    Endpoint::get_with(String::from("/v1/categories"), &params)
+}
+
+pub(crate) fn search_mods(params: &SearchModsParameters) -> CurseForgeResult<Endpoint> {
+    Endpoint::get_with(String::from("/v1/mods/search"), params)
+}
+
+pub(crate) fn get_mod(mod_id: u32) -> Endpoint {
+    Endpoint::get(format!("/v1/mods/{}", mod_id))
+}
+
+pub(crate) fn get_mod_description(mod_id: u32) -> Endpoint {
+    Endpoint::get(format!("/v1/mods/{}/description", mod_id))
+}
+
+/// 每批最多[`BATCH_SIZE`](batch::BATCH_SIZE)个编号，没有编号时不发送请求
+pub(crate) fn get_mods(
+    mod_ids: &[u32],
+    filter_pc_only: Option<bool>,
+) -> CurseForgeResult<Vec<Endpoint>> {
+    batch::dedup(mod_ids)
+        .chunks(batch::BATCH_SIZE)
+        .map(|chunk| {
+            let body = GetModsBody {
+                mod_ids: chunk,
+                filter_pc_only,
+            };
+            Endpoint::post(String::from("/v1/mods"), &body).map(Endpoint::idempotent)
+        })
+        .collect()
+}
+
+pub(crate) fn get_featured_mods(
+    game_id: u32,
+    excluded_mod_ids: &[u32],
+    game_version_type_id: Option<u32>,
+) -> CurseForgeResult<Endpoint> {
+    let body = GetFeaturedModsBody {
+        game_id,
+        excluded_mod_ids,
+        game_version_type_id,
+    };
+    Endpoint::post(String::from("/v1/mods/featured"), &body).map(Endpoint::idempotent)
+}
+
+pub(crate) fn get_mod_file(mod_id: u32, file_id: u32) -> Endpoint {
+    Endpoint::get(format!("/v1/mods/{}/files/{}", mod_id, file_id))
+}
+
+pub(crate) fn get_mod_file_changelog(mod_id: u32, file_id: u32) -> Endpoint {
+    Endpoint::get(format!("/v1/mods/{}/files/{}/changelog", mod_id, file_id))
+}
+
+pub(crate) fn get_mod_file_download_url(mod_id: u32, file_id: u32) -> Endpoint {
+    Endpoint::get(format!(
+        "/v1/mods/{}/files/{}/download-url",
+        mod_id, file_id
+    ))
+}
+
+pub(crate) fn get_mod_files(
+    mod_id: u32,
+    options: &GetModFilesParameters,
+) -> CurseForgeResult<Endpoint> {
+    Endpoint::get_with(format!("/v1/mods/{}/files", mod_id), options)
+}
+
+/// 每批最多[`BATCH_SIZE`](batch::BATCH_SIZE)个编号，没有编号时不发送请求
+pub(crate) fn get_files(file_ids: &[u32]) -> CurseForgeResult<Vec<Endpoint>> {
+    batch::dedup(file_ids)
+        .chunks(batch::BATCH_SIZE)
+        .map(|chunk| {
+            let body = GetFilesBody { file_ids: chunk };
+            Endpoint::post(String::from("/v1/mods/files"), &body).map(Endpoint::idempotent)
+        })
+        .collect()
+}
+
+pub(crate) fn get_fingerprint_matches(
+    game_id: Option<u32>,
+    fingerprints: &[u32],
+) -> CurseForgeResult<Endpoint> {
+    let path = match game_id {
+        Some(game_id) => format!("/v1/fingerprints/{}", game_id),
+        None => String::from("/v1/fingerprints"),
+    };
+    Endpoint::post(path, &GetFingerprintMatchesBody { fingerprints }).map(Endpoint::idempotent)
+}
+
+/// `by_game`为`true`时使用`/v1/fingerprints/fuzzy/{gameId}`
+pub(crate) fn get_fingerprint_fuzzy_matches(
+    game_id: u32,
+    by_game: bool,
+    fingerprints: &[FolderFingerprint],
+) -> CurseForgeResult<Endpoint> {
+    let path = if by_game {
+        format!("/v1/fingerprints/fuzzy/{}", game_id)
+    } else {
+        String::from("/v1/fingerprints/fuzzy")
+    };
+    let body = GetFingerprintFuzzyMatchesBody {
+        game_id,
+        fingerprints,
+    };
+    Endpoint::post(path, &body).map(Endpoint::idempotent)
+}
+
+pub(crate) fn get_minecraft_versions(sort_descending: Option<bool>) -> CurseForgeResult<Endpoint> {
+    let mut params: Vec<(&str, String)> = vec![];
+    if let Some(sort_descending) = sort_descending {
+        params.push(("sortDescending", sort_descending.to_string()));
+    }
+    Endpoint::get_with(String::from("/v1/minecraft/version"), &params)
+}
+
+pub(crate) fn get_minecraft_version(game_version: &str) -> CurseForgeResult<Endpoint> {
+    let path = format!("/v1/minecraft/version/{}", path_segment(game_version)?);
+    Ok(Endpoint::get(path))
+}
+
+pub(crate) fn get_minecraft_mod_loaders(
+    version: Option<&str>,
+    include_all: Option<bool>,
+) -> CurseForgeResult<Endpoint> {
+    let mut params: Vec<(&str, String)> = vec![];
+    if let Some(version) = version {
+        params.push(("version", version.to_string()));
+    }
+    if let Some(include_all) = include_all {
+        params.push(("includeAll", include_all.to_string()));
+    }
+    Endpoint::get_with(String::from("/v1/minecraft/modloader"), &params)
+}
+
+pub(crate) fn get_minecraft_mod_loader(mod_loader_name: &str) -> CurseForgeResult<Endpoint> {
+    let path = format!("/v1/minecraft/modloader/{}", path_segment(mod_loader_name)?);
+    Ok(Endpoint::get(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE_URL: &str = "https://api.curseforge.com";
+
+    #[test]
+    fn test_path_segment() {
+        assert_eq!(path_segment("1.20.1").unwrap(), "1.20.1");
+        assert_eq!(path_segment("forge-47.2.0").unwrap(), "forge-47.2.0");
+        assert_eq!(path_segment("a/b?c#d").unwrap(), "a%2Fb%3Fc%23d");
+        assert!(matches!(path_segment(".."), Err(Error::Config(_))));
+        assert!(matches!(path_segment(""), Err(Error::Config(_))));
+    }
+
+    #[test]
+    fn test_url() {
+        let url = get_game(432).url(BASE_URL).unwrap();
+        assert_eq!(url.as_str(), "https://api.curseforge.com/v1/games/432");
+
+        let params = SearchModsParameters::new(432).search_filter("just enough items");
+        let url = search_mods(&params).unwrap().url(BASE_URL).unwrap();
+        assert_eq!(
+            url.query(),
+            Some("gameId=432&searchFilter=just+enough+items")
+        );
+
+        let result = get_game(432).url("not a url");
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
+
+    #[test]
+    fn test_request() {
+        let request = get_games(Some(50), None)
+            .unwrap()
+            .request(BASE_URL, "test")
+            .unwrap();
+        assert_eq!(request.method, Method::GET);
+        assert_eq!(request.url.query(), Some("index=50"));
+        assert_eq!(request.headers.get("x-api-key").unwrap(), "test");
+        assert!(request.body.is_none());
+
+        let request = get_fingerprint_matches(Some(432), &[1, 2])
+            .unwrap()
+            .request(BASE_URL, "test")
+            .unwrap();
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.url.path(), "/v1/fingerprints/432");
+        assert!(get_fingerprint_matches(None, &[1]).unwrap().idempotent);
+        assert_eq!(
+            request.body.as_deref(),
+            Some(&br#"{"fingerprints":[1,2]}"#[..])
+        );
+        assert_eq!(
+            request.headers.get("content-type").unwrap(),
+            "application/json"
+        );
+    }
+
+    #[test]
+    fn test_batches() {
+        let mod_ids: Vec<u32> = (1..=150).chain([1, 2]).collect();
+        let endpoints = get_mods(&mod_ids, None).unwrap();
+        assert_eq!(endpoints.len(), 2);
+        assert!(get_files(&[]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,280 @@
+use std::io::ErrorKind;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+#[cfg(feature = "blocking")]
+use super::transport::Transport;
+#[cfg(feature = "async")]
+use super::transport::{AsyncTransport, TransportFuture};
+use super::transport::{HttpRequest, HttpResponse};
+use crate::curseforge::error::{CurseForgeResult, Error};
+
+/// 注入的故障
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fault {
+    /// 发送前等待指定时间，可以和其他故障叠加
+    Latency(Duration),
+
+    /// 不发送请求，返回连接被重置的传输错误
+    ConnectionReset,
+
+    /// 不发送请求，返回指定状态码的响应
+    Status(u16),
+
+    /// 正常发送请求，只返回一半的响应体
+    TruncatedBody,
+
+    /// 正常发送请求，将响应体替换为无法解析的JSON
+    MalformedJson,
+}
+
+#[derive(Debug, Clone)]
+struct FaultRule {
+    path_prefix: Option<String>,
+    fault: Fault,
+    rate: f64,
+}
+
+/// 按比例注入故障的传输层，用于测试重试和超时处理
+///
+/// 每个请求按添加顺序检查匹配的规则，并以规则的概率触发故障；
+/// 延迟会累加，其余故障只触发第一个。随机数由种子生成，
+/// 相同的种子和请求顺序总是得到相同的结果。
+///
+/// 内部传输层实现`Transport`时可用于阻塞客户端，
+/// 实现`AsyncTransport`时可用于异步客户端。
+/// 通过`ClientBuilder::wrap_transport`或`wrap_async_transport`
+/// 包装默认传输层可以保留超时、代理等HTTP设置。
+///
+/// ```no_run
+/// # #[cfg(feature = "blocking")]
+/// # fn main() -> Result<(), curseforge_api::error::Error> {
+/// use std::time::Duration;
+///
+/// use curseforge_api::{Client, Fault, FaultTransport, RetryPolicy};
+///
+/// let client = Client::builder()
+///     .timeout(Duration::from_secs(10))
+///     .retry_policy(RetryPolicy::new())
+///     .wrap_transport(|inner| {
+///         FaultTransport::new(inner, 42)
+///             .fault(Fault::Status(503), 0.2)
+///             .fault_for("/v1/mods/search", Fault::ConnectionReset, 0.5)
+///     })
+///     .build()?;
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "blocking"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone)]
+pub struct FaultTransport<T> {
+    inner: T,
+    rules: Vec<FaultRule>,
+    rng: Arc<Mutex<fastrand::Rng>>,
+}
+
+impl<T> std::fmt::Debug for FaultTransport<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FaultTransport")
+            .field("rules", &self.rules)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> FaultTransport<T> {
+    pub fn new(inner: T, seed: u64) -> Self {
+        Self {
+            inner,
+            rules: vec![],
+            rng: Arc::new(Mutex::new(fastrand::Rng::with_seed(seed))),
+        }
+    }
+
+    /// 对所有请求以`rate`（0.0 ~ 1.0）的概率注入故障
+    pub fn fault(mut self, fault: Fault, rate: f64) -> Self {
+        self.rules.push(FaultRule {
+            path_prefix: None,
+            fault,
+            rate,
+        });
+        self
+    }
+
+    /// 对路径以`path_prefix`开头的请求以`rate`的概率注入故障
+    pub fn fault_for(mut self, path_prefix: &str, fault: Fault, rate: f64) -> Self {
+        self.rules.push(FaultRule {
+            path_prefix: Some(path_prefix.to_string()),
+            fault,
+            rate,
+        });
+        self
+    }
+
+    /// 决定本次请求的延迟和故障
+    fn roll(&self, request: &HttpRequest) -> (Duration, Option<Fault>) {
+        let mut rng = self.rng.lock().unwrap_or_else(|e| e.into_inner());
+        let mut latency = Duration::ZERO;
+        let mut fault = None;
+        let rules = self.rules.iter().filter(|rule| match &rule.path_prefix {
+            Some(prefix) => request.url.path().starts_with(prefix.as_str()),
+            None => true,
+        });
+        for rule in rules {
+            // 每条规则都消耗一个随机数，保证结果只取决于种子和请求顺序
+            if rng.f64() >= rule.rate {
+                continue;
+            }
+            match &rule.fault {
+                Fault::Latency(duration) => latency += *duration,
+                other if fault.is_none() => fault = Some(other.clone()),
+                _ => {}
+            }
+        }
+        (latency, fault)
+    }
+}
+
+/// 发送请求前触发的故障，返回`None`时正常发送
+fn before_send(fault: &Fault) -> Option<CurseForgeResult<HttpResponse>> {
+    match fault {
+        Fault::ConnectionReset => Some(Err(Error::Transport(Box::new(std::io::Error::new(
+            ErrorKind::ConnectionReset,
+            "injected connection reset",
+        ))))),
+        Fault::Status(status) => Some(
+            StatusCode::from_u16(*status)
+                .map(|status| HttpResponse::new(status, "injected fault"))
+                .map_err(|_| Error::Config(format!("invalid fault status {}", status))),
+        ),
+        _ => None,
+    }
+}
+
+/// 收到响应后触发的故障
+fn after_send(fault: &Fault, mut response: HttpResponse) -> HttpResponse {
+    match fault {
+        Fault::TruncatedBody => response.body.truncate(response.body.len() / 2),
+        Fault::MalformedJson => response.body = b"{\"data\": [".to_vec(),
+        _ => {}
+    }
+    response
+}
+
+#[cfg(feature = "blocking")]
+impl<T: Transport> Transport for FaultTransport<T> {
+    fn send(&self, request: HttpRequest) -> CurseForgeResult<HttpResponse> {
+        let (latency, fault) = self.roll(&request);
+        if !latency.is_zero() {
+            tracing::debug!(latency_ms = latency.as_millis() as u64, "injecting latency");
+            std::thread::sleep(latency);
+        }
+        let Some(fault) = fault else {
+            return self.inner.send(request);
+        };
+
+        tracing::debug!(?fault, url = %request.url, "injecting fault");
+        if let Some(result) = before_send(&fault) {
+            return result;
+        }
+        Ok(after_send(&fault, self.inner.send(request)?))
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T: AsyncTransport> AsyncTransport for FaultTransport<T> {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let (latency, fault) = self.roll(&request);
+            if !latency.is_zero() {
+                tracing::debug!(latency_ms = latency.as_millis() as u64, "injecting latency");
+                tokio::time::sleep(latency).await;
+            }
+            let Some(fault) = fault else {
+                return self.inner.send(request).await;
+            };
+
+            tracing::debug!(?fault, url = %request.url, "injecting fault");
+            if let Some(result) = before_send(&fault) {
+                return result;
+            }
+            Ok(after_send(&fault, self.inner.send(request).await?))
+        })
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod tests {
+    use super::*;
+    use crate::curseforge::client::FixtureTransport;
+    use reqwest::{Method, Url};
+
+    fn fixtures() -> FixtureTransport {
+        FixtureTransport::new()
+            .json("/v1/games/432", 200, r#"{"data":{"id":432}}"#)
+            .json("/v1/mods/search", 200, r#"{"data":[]}"#)
+    }
+
+    fn get(
+        transport: &FaultTransport<FixtureTransport>,
+        path: &str,
+    ) -> CurseForgeResult<HttpResponse> {
+        let url = Url::parse(&format!("http://localhost{}", path)).unwrap();
+        Transport::send(transport, HttpRequest::new(Method::GET, url))
+    }
+
+    fn outcomes(seed: u64) -> Vec<Option<u16>> {
+        let transport = FaultTransport::new(fixtures(), seed)
+            .fault(Fault::ConnectionReset, 0.3)
+            .fault(Fault::Status(429), 0.3);
+        (0..50)
+            .map(|_| {
+                get(&transport, "/v1/games/432")
+                    .ok()
+                    .map(|resp| resp.status.as_u16())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_reproducible_from_seed() {
+        let first = outcomes(7);
+        assert_eq!(first, outcomes(7));
+        assert_ne!(first, outcomes(8));
+        assert!(first.contains(&None));
+        assert!(first.contains(&Some(429)));
+        assert!(first.contains(&Some(200)));
+    }
+
+    #[test]
+    fn test_faults() {
+        let transport = FaultTransport::new(fixtures(), 1).fault(Fault::ConnectionReset, 1.0);
+        let error = get(&transport, "/v1/games/432").unwrap_err();
+        assert!(crate::RetryPolicy::new().is_retryable_error(&error));
+
+        let transport = FaultTransport::new(fixtures(), 1).fault(Fault::TruncatedBody, 1.0);
+        let body = get(&transport, "/v1/games/432").unwrap().body;
+        assert_eq!(body, br#"{"data":{"#);
+
+        let transport = FaultTransport::new(fixtures(), 1).fault(Fault::MalformedJson, 1.0);
+        let body = get(&transport, "/v1/games/432").unwrap().body;
+        assert!(serde_json::from_slice::<serde_json::Value>(&body).is_err());
+
+        let transport = FaultTransport::new(fixtures(), 1)
+            .fault(Fault::Latency(Duration::from_millis(20)), 1.0)
+            .fault(Fault::Status(503), 0.0);
+        let started = std::time::Instant::now();
+        assert_eq!(get(&transport, "/v1/games/432").unwrap().status, 200);
+        assert!(started.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn test_fault_for_endpoint() {
+        let transport =
+            FaultTransport::new(fixtures(), 1).fault_for("/v1/mods", Fault::Status(500), 1.0);
+        assert_eq!(get(&transport, "/v1/games/432").unwrap().status, 200);
+        assert_eq!(get(&transport, "/v1/mods/search").unwrap().status, 500);
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::transport::{HttpRequest, HttpResponse};
+use crate::curseforge::error::{CurseForgeResult, Error};
+
+/// 请求中间件
+///
+/// 每次实际发送请求（包括重试）前后调用，命中缓存的请求不会经过中间件。
+/// 可用于记录日志、统计指标或为请求签名。
+pub trait Middleware: Send + Sync {
+    /// 发送前调用，可以修改请求；返回错误时放弃发送
+    fn on_request(&self, request: &mut HttpRequest) -> CurseForgeResult<()> {
+        let _ = request;
+        Ok(())
+    }
+
+    /// 收到响应后调用，非2xx响应同样会调用
+    fn on_response(&self, request: &HttpRequest, response: &HttpResponse, elapsed: Duration) {
+        let _ = (request, response, elapsed);
+    }
+
+    /// 传输失败时调用
+    fn on_error(&self, request: &HttpRequest, error: &Error, elapsed: Duration) {
+        let _ = (request, error, elapsed);
+    }
+}
+
+/// 按添加顺序执行的中间件
+#[derive(Clone, Default)]
+pub(crate) struct MiddlewareChain {
+    middlewares: Vec<Arc<dyn Middleware>>,
+}
+
+impl MiddlewareChain {
+    pub(crate) fn push(&mut self, middleware: Arc<dyn Middleware>) {
+        self.middlewares.push(middleware);
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.middlewares.len()
+    }
+
+    pub(crate) fn before(&self, request: &mut HttpRequest) -> CurseForgeResult<()> {
+        for middleware in &self.middlewares {
+            middleware.on_request(request)?;
+        }
+        Ok(())
+    }
+
+    /// 记录本次发送的结果并通知中间件
+    pub(crate) fn after(
+        &self,
+        request: &HttpRequest,
+        result: &CurseForgeResult<HttpResponse>,
+        elapsed: Duration,
+    ) {
+        let latency_ms = elapsed.as_millis() as u64;
+        match result {
+            Ok(response) => {
+                tracing::debug!(
+                    status = response.status.as_u16(),
+                    latency_ms,
+                    bytes = response.body.len(),
+                    "response received"
+                );
+                for middleware in &self.middlewares {
+                    middleware.on_response(request, response, elapsed);
+                }
+            }
+            Err(error) => {
+                tracing::debug!(latency_ms, error = %error, "transport failed");
+                for middleware in &self.middlewares {
+                    middleware.on_error(request, error, elapsed);
+                }
+            }
+        }
+    }
+}
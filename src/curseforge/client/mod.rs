@@ -1,5 +1,35 @@
+#[cfg(feature = "async")]
+mod async_client;
+mod batch;
 mod builder;
+mod cache;
+mod cassette;
+#[cfg(feature = "blocking")]
+#[allow(clippy::module_inception)]
 mod client;
+mod endpoints;
+mod fault;
+mod http_options;
+mod middleware;
+mod rate_limit;
+mod retry;
+#[cfg(test)]
+mod testing;
+mod transport;
 
+#[cfg(feature = "async")]
+pub use async_client::AsyncClient;
 pub use builder::ClientBuilder;
+pub(crate) use builder::ClientConfig;
+pub use cache::CacheOptions;
+pub use cassette::CassetteTransport;
+#[cfg(feature = "blocking")]
 pub use client::Client;
+pub use fault::{Fault, FaultTransport};
+pub use middleware::Middleware;
+pub use retry::RetryPolicy;
+#[cfg(feature = "async")]
+pub use transport::{AsyncReqwestTransport, AsyncTransport, TransportFuture};
+pub use transport::{FixtureTransport, HttpRequest, HttpResponse};
+#[cfg(feature = "blocking")]
+pub use transport::{ReqwestTransport, Transport};
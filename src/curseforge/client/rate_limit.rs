@@ -0,0 +1,126 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 令牌桶限流器
+///
+/// 令牌以固定速率补充，桶容量即允许的突发请求数。
+/// 令牌不足时预支未来的令牌，并返回调用方需要等待的时间，
+/// 因此并发请求会按到达顺序依次排开。
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    requests_per_second: f64,
+    burst: f64,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(requests_per_second: f64, burst: u32) -> Self {
+        let burst = f64::from(burst.max(1));
+        Self {
+            requests_per_second,
+            burst,
+            state: Mutex::new(BucketState {
+                tokens: burst,
+                updated_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// 取走一个令牌，返回发送请求前需要等待的时间
+    pub(crate) fn reserve(&self) -> Duration {
+        self.reserve_at(Instant::now())
+    }
+
+    fn reserve_at(&self, now: Instant) -> Duration {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let elapsed = now
+            .saturating_duration_since(state.updated_at)
+            .as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.requests_per_second).min(self.burst);
+        state.updated_at = now.max(state.updated_at);
+        state.tokens -= 1.0;
+        if state.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            // 速率极低时等待时间可能超出Duration的范围
+            Duration::try_from_secs_f64(-state.tokens / self.requests_per_second)
+                .unwrap_or(Duration::MAX)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_then_throttle() {
+        let limiter = RateLimiter::new(10.0, 2);
+        let now = Instant::now();
+        assert_eq!(limiter.reserve_at(now), Duration::ZERO);
+        assert_eq!(limiter.reserve_at(now), Duration::ZERO);
+
+        let wait = limiter.reserve_at(now);
+        assert!((wait.as_secs_f64() - 0.1).abs() < 1e-6);
+
+        let wait = limiter.reserve_at(now);
+        assert!((wait.as_secs_f64() - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_refill() {
+        let limiter = RateLimiter::new(10.0, 1);
+        let now = Instant::now();
+        assert_eq!(limiter.reserve_at(now), Duration::ZERO);
+        assert!(limiter.reserve_at(now) > Duration::ZERO);
+
+        // 0.1秒的欠账在1秒后早已还清，桶也不会超过容量
+        let later = now + Duration::from_secs(1);
+        assert_eq!(limiter.reserve_at(later), Duration::ZERO);
+        assert!(limiter.reserve_at(later) > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_shared_across_threads() {
+        let limiter = std::sync::Arc::new(RateLimiter::new(100.0, 5));
+        let now = Instant::now();
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let limiter = limiter.clone();
+                std::thread::spawn(move || {
+                    (0..5).map(|_| limiter.reserve_at(now)).collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let mut waits: Vec<Duration> = handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect();
+        waits.sort();
+
+        // 20个请求，5个突发，其余15个以每秒100个的速率依次排开
+        assert_eq!(waits[..5], [Duration::ZERO; 5]);
+        for (i, wait) in waits[5..].iter().enumerate() {
+            let expected = (i + 1) as f64 * 0.01;
+            assert!(
+                (wait.as_secs_f64() - expected).abs() < 1e-6,
+                "wait = {:?}",
+                wait
+            );
+        }
+    }
+
+    #[test]
+    fn test_tiny_rate_does_not_panic() {
+        let limiter = RateLimiter::new(1e-300, 1);
+        let now = Instant::now();
+        assert_eq!(limiter.reserve_at(now), Duration::ZERO);
+        assert_eq!(limiter.reserve_at(now), Duration::MAX);
+    }
+}
@@ -0,0 +1,358 @@
+use std::io::ErrorKind;
+use std::time::{Duration, SystemTime};
+
+use reqwest::StatusCode;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+use super::transport::HttpResponse;
+use crate::curseforge::error::{CurseForgeResult, Error};
+
+/// 重试策略
+///
+/// 仅对幂等请求生效：GET请求，以及批量获取模组和文件、获取推荐模组、
+/// 指纹匹配等只读取数据的POST请求。
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retry_statuses: Vec<u16>,
+    retry_transport_errors: bool,
+    respect_retry_after: bool,
+}
+
+/// 一次尝试之后的下一步
+#[derive(Debug)]
+pub(crate) enum Retry {
+    /// 不再重试，返回结果
+    Done(CurseForgeResult<HttpResponse>),
+
+    /// 等待指定时间后重试
+    After(Duration),
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RetryPolicy {
+    /// 默认策略：最多尝试3次，退避从500毫秒开始翻倍，最长30秒，
+    /// 重试429、500、502、503、504以及连接失败和超时
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            retry_statuses: vec![429, 500, 502, 503, 504],
+            retry_transport_errors: true,
+            respect_retry_after: true,
+        }
+    }
+
+    /// 不重试
+    pub fn none() -> Self {
+        Self::new().max_attempts(1)
+    }
+
+    /// 最大尝试次数（包含首次请求）
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// 首次重试前的等待时间，之后每次翻倍
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// 单次等待时间上限，同样作用于`Retry-After`
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// 是否在退避时间上加入随机抖动
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// 需要重试的HTTP状态码
+    pub fn retry_statuses(mut self, retry_statuses: &[u16]) -> Self {
+        self.retry_statuses = retry_statuses.to_vec();
+        self
+    }
+
+    /// 是否重试连接失败和超时
+    pub fn retry_transport_errors(mut self, retry_transport_errors: bool) -> Self {
+        self.retry_transport_errors = retry_transport_errors;
+        self
+    }
+
+    /// 是否使用响应中`Retry-After`指定的等待时间
+    pub fn respect_retry_after(mut self, respect_retry_after: bool) -> Self {
+        self.respect_retry_after = respect_retry_after;
+        self
+    }
+
+    /// 请求允许的最大尝试次数，非幂等请求只尝试一次
+    fn max_attempts_for(&self, idempotent: bool) -> u32 {
+        if idempotent { self.max_attempts } else { 1 }
+    }
+
+    fn is_retryable_status(&self, status: u16) -> bool {
+        self.retry_statuses.contains(&status)
+    }
+
+    pub(crate) fn is_retryable_error(&self, error: &Error) -> bool {
+        match error {
+            Error::Transport(source) => {
+                self.retry_transport_errors && is_transient(source.as_ref())
+            }
+            _ => false,
+        }
+    }
+
+    /// 根据第`attempt`次尝试的结果决定返回结果还是等待后重试，
+    /// 成功和304响应直接返回，其他响应转换为[`Error::Status`]
+    pub(crate) fn next(
+        &self,
+        idempotent: bool,
+        attempt: u32,
+        result: CurseForgeResult<HttpResponse>,
+    ) -> Retry {
+        let (error, retry_after) = match result {
+            Ok(response)
+                if response.status.is_success() || response.status == StatusCode::NOT_MODIFIED =>
+            {
+                return Retry::Done(Ok(response));
+            }
+            Ok(response) => {
+                let status = response.status.as_u16();
+                let error = Error::status(status, &response.body);
+                if !self.is_retryable_status(status) {
+                    return Retry::Done(Err(error));
+                }
+                (error, retry_after(&response.headers))
+            }
+            Err(error) => {
+                if !self.is_retryable_error(&error) {
+                    return Retry::Done(Err(error));
+                }
+                (error, None)
+            }
+        };
+
+        if attempt >= self.max_attempts_for(idempotent) {
+            return Retry::Done(Err(error));
+        }
+        let backoff = self.backoff(attempt, retry_after);
+        tracing::info!(
+            attempt,
+            backoff_ms = backoff.as_millis() as u64,
+            error = %error,
+            "retrying request"
+        );
+        Retry::After(backoff)
+    }
+
+    /// 第`attempt`次尝试失败后的等待时间
+    pub(crate) fn backoff(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after.filter(|_| self.respect_retry_after) {
+            return retry_after.min(self.max_backoff);
+        }
+
+        let exponent = attempt.saturating_sub(1).min(31);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff);
+        if self.jitter {
+            // 等量抖动：保留一半，另一半随机
+            let half = backoff / 2;
+            half + half.mul_f64(fastrand::f64())
+        } else {
+            backoff
+        }
+    }
+}
+
+/// 连接失败、超时、连接被重置等可能自行恢复的传输错误
+///
+/// 沿`source()`链逐层检查，被其他错误包装的IO错误同样视为暂时性错误。
+fn is_transient(error: &(dyn std::error::Error + 'static)) -> bool {
+    let mut current = Some(error);
+    while let Some(error) = current {
+        if let Some(error) = error.downcast_ref::<reqwest::Error>() {
+            // 发送过程中连接被服务器关闭（如hyper的IncompleteMessage）属于请求错误
+            if error.is_timeout() || error.is_connect() || error.is_request() {
+                return true;
+            }
+        }
+        if let Some(error) = error.downcast_ref::<std::io::Error>()
+            && matches!(
+                error.kind(),
+                ErrorKind::ConnectionRefused
+                    | ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::BrokenPipe
+                    | ErrorKind::TimedOut
+                    | ErrorKind::UnexpectedEof
+            )
+        {
+            return true;
+        }
+        current = error.source();
+    }
+    false
+}
+
+/// 解析`Retry-After`响应头，支持秒数和HTTP日期两种格式
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_exponential() {
+        let policy = RetryPolicy::new()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(1000))
+            .jitter(false);
+        assert_eq!(policy.backoff(1, None), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2, None), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3, None), Duration::from_millis(400));
+        assert_eq!(policy.backoff(5, None), Duration::from_millis(1000));
+        assert_eq!(policy.backoff(100, None), Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn test_backoff_jitter() {
+        let policy = RetryPolicy::new().initial_backoff(Duration::from_millis(100));
+        for _ in 0..100 {
+            let backoff = policy.backoff(2, None);
+            assert!(backoff >= Duration::from_millis(100));
+            assert!(backoff <= Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn test_backoff_retry_after() {
+        let policy = RetryPolicy::new().max_backoff(Duration::from_secs(10));
+        let backoff = policy.backoff(1, Some(Duration::from_secs(3)));
+        assert_eq!(backoff, Duration::from_secs(3));
+        let backoff = policy.backoff(1, Some(Duration::from_secs(60)));
+        assert_eq!(backoff, Duration::from_secs(10));
+
+        let policy = policy.respect_retry_after(false).jitter(false);
+        let backoff = policy.backoff(1, Some(Duration::from_secs(3)));
+        assert_eq!(backoff, Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_max_attempts_idempotent_only() {
+        let policy = RetryPolicy::new().max_attempts(5);
+        assert_eq!(policy.max_attempts_for(true), 5);
+        assert_eq!(policy.max_attempts_for(false), 1);
+        assert_eq!(RetryPolicy::none().max_attempts_for(true), 1);
+    }
+
+    #[test]
+    fn test_next() {
+        let policy = RetryPolicy::new().max_attempts(2).jitter(false);
+        let response =
+            |status: u16| Ok(HttpResponse::new(StatusCode::from_u16(status).unwrap(), ""));
+
+        assert!(matches!(
+            policy.next(true, 1, response(200)),
+            Retry::Done(Ok(_))
+        ));
+        assert!(matches!(
+            policy.next(true, 1, response(304)),
+            Retry::Done(Ok(_))
+        ));
+        assert!(matches!(
+            policy.next(true, 1, response(404)),
+            Retry::Done(Err(Error::Status { status: 404, .. }))
+        ));
+        assert!(matches!(
+            policy.next(true, 1, response(503)),
+            Retry::After(backoff) if backoff == Duration::from_millis(500)
+        ));
+        assert!(matches!(
+            policy.next(true, 2, response(503)),
+            Retry::Done(Err(Error::Status { status: 503, .. }))
+        ));
+        assert!(matches!(
+            policy.next(false, 1, response(503)),
+            Retry::Done(Err(Error::Status { status: 503, .. }))
+        ));
+    }
+
+    /// 将内部错误作为`source()`的包装错误
+    #[derive(Debug)]
+    struct Wrapped(std::io::Error);
+
+    impl std::fmt::Display for Wrapped {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "request failed")
+        }
+    }
+
+    impl std::error::Error for Wrapped {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn test_retryable_error() {
+        let policy = RetryPolicy::new();
+        let reset = std::io::Error::from(ErrorKind::ConnectionReset);
+        assert!(policy.is_retryable_error(&Error::Transport(Box::new(reset))));
+        let other = std::io::Error::other("boom");
+        assert!(!policy.is_retryable_error(&Error::Transport(Box::new(other))));
+        assert!(!policy.is_retryable_error(&Error::Config(String::new())));
+
+        let wrapped = Wrapped(std::io::Error::from(ErrorKind::UnexpectedEof));
+        assert!(policy.is_retryable_error(&Error::Transport(Box::new(wrapped))));
+
+        let policy = policy.retry_transport_errors(false);
+        let reset = std::io::Error::from(ErrorKind::ConnectionReset);
+        assert!(!policy.is_retryable_error(&Error::Transport(Box::new(reset))));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, "120".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(120)));
+
+        headers.insert(
+            RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+
+        headers.insert(RETRY_AFTER, "soon".parse().unwrap());
+        assert_eq!(retry_after(&headers), None);
+    }
+}
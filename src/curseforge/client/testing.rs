@@ -0,0 +1,99 @@
+//! 阻塞客户端和异步客户端共用的测试工具
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::Middleware;
+use super::transport::{HttpRequest, HttpResponse};
+use crate::curseforge::error::CurseForgeResult;
+
+pub(crate) const MINECRAFT: &str = r#"{"data": {
+    "id": 432,
+    "name": "Minecraft",
+    "slug": "minecraft",
+    "dateModified": "2024-08-29T09:54:14.45Z",
+    "assets": {"iconUrl": "", "tileUrl": "", "coverUrl": ""},
+    "status": 6,
+    "apiStatus": 2
+}}"#;
+
+/// 为每个请求加上`x-request-id`并统计收到的响应
+#[derive(Default)]
+pub(crate) struct Counter {
+    pub(crate) responses: Arc<AtomicUsize>,
+}
+
+impl Middleware for Counter {
+    fn on_request(&self, request: &mut HttpRequest) -> CurseForgeResult<()> {
+        request.headers.insert(
+            "x-request-id",
+            reqwest::header::HeaderValue::from_static("1"),
+        );
+        Ok(())
+    }
+
+    fn on_response(&self, _: &HttpRequest, _: &HttpResponse, _: Duration) {
+        self.responses.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// 依次返回给定响应的本地HTTP服务器，返回地址和收到的请求头（第一行为请求行）
+///
+/// 状态码为0时读取请求后直接关闭连接，不返回响应。
+pub(crate) fn serve(responses: Vec<(u16, &'static str)>) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(vec![]));
+    let received = requests.clone();
+    std::thread::spawn(move || {
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut head = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim_end().is_empty() {
+                    break;
+                }
+                head.push_str(&line);
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            reader.read_exact(&mut vec![0; content_length]).unwrap();
+            received.lock().unwrap().push(head);
+            if status == 0 {
+                continue;
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    (base_url, requests)
+}
+
+/// 接受连接但从不返回响应的本地HTTP服务器，返回地址
+pub(crate) fn stall() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        let mut connections = vec![];
+        for stream in listener.incoming() {
+            connections.push(stream);
+        }
+    });
+    base_url
+}
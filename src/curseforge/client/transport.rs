@@ -0,0 +1,355 @@
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "async")]
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::{Method, StatusCode, Url};
+use serde::de::DeserializeOwned;
+
+use super::http_options::HttpOptions;
+use crate::curseforge::error::{CurseForgeResult, Error, decode};
+
+/// HTTP请求
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap,
+    pub body: Option<Vec<u8>>,
+}
+
+impl HttpRequest {
+    pub fn new(method: Method, url: Url) -> Self {
+        Self {
+            method,
+            url,
+            headers: HeaderMap::new(),
+            body: None,
+        }
+    }
+
+    /// 设置已序列化的JSON请求体
+    pub(crate) fn json(mut self, body: Vec<u8>) -> Self {
+        self.headers.insert(
+            reqwest::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        self.body = Some(body);
+        self
+    }
+}
+
+/// 读取完毕的HTTP响应
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn new(status: StatusCode, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+}
+
+/// 构造带有API Key的请求
+pub(crate) fn api_request(
+    method: Method,
+    url: Url,
+    api_key: &str,
+) -> CurseForgeResult<HttpRequest> {
+    let mut api_key = HeaderValue::from_str(api_key)
+        .map_err(|_| Error::Config(String::from("API key is not a valid header value")))?;
+    // 避免在请求的Debug输出中泄露API Key
+    api_key.set_sensitive(true);
+    let mut request = HttpRequest::new(method, url);
+    request.headers.insert("x-api-key", api_key);
+    Ok(request)
+}
+
+/// 一次API调用的tracing span，包含重试在内的所有日志都记录在其中
+pub(crate) fn request_span(path: &str, request: &HttpRequest) -> tracing::Span {
+    tracing::info_span!(
+        "curseforge_request",
+        method = %request.method,
+        endpoint = path,
+        query = request.url.query().unwrap_or_default(),
+    )
+}
+
+/// 记录API调用的结果并解析响应体，`started`为调用开始的时间
+pub(crate) fn complete<T>(
+    started: Instant,
+    result: CurseForgeResult<Vec<u8>>,
+) -> CurseForgeResult<T>
+where
+    T: DeserializeOwned,
+{
+    let result = result.and_then(|body| {
+        tracing::info!(
+            latency_ms = started.elapsed().as_millis() as u64,
+            bytes = body.len(),
+            "request completed"
+        );
+        decode(&body)
+    });
+    if let Err(error) = &result {
+        tracing::warn!(error = %error, "request failed");
+    }
+    result
+}
+
+/// HTTP传输层
+///
+/// 客户端通过它发送所有请求，可替换为自定义实现或用于测试的[`FixtureTransport`]。
+/// 非2xx响应应当正常返回，由客户端负责转换为错误。
+#[cfg(feature = "blocking")]
+pub trait Transport: Send + Sync {
+    fn send(&self, request: HttpRequest) -> CurseForgeResult<HttpResponse>;
+}
+
+/// [`AsyncTransport::send`]返回的Future
+#[cfg(feature = "async")]
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = CurseForgeResult<HttpResponse>> + Send + 'a>>;
+
+/// 异步客户端的HTTP传输层，约定与阻塞客户端的`Transport`相同
+#[cfg(feature = "async")]
+pub trait AsyncTransport: Send + Sync {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_>;
+}
+
+/// 基于`reqwest::blocking`的默认传输层
+#[cfg(feature = "blocking")]
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    http_client: reqwest::blocking::Client,
+}
+
+#[cfg(feature = "blocking")]
+impl ReqwestTransport {
+    pub fn new() -> CurseForgeResult<Self> {
+        Self::with_options(&HttpOptions::default())
+    }
+
+    pub(crate) fn with_options(options: &HttpOptions) -> CurseForgeResult<Self> {
+        Ok(Self::from_client(options.blocking_client()?))
+    }
+
+    pub fn from_client(http_client: reqwest::blocking::Client) -> Self {
+        Self { http_client }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> CurseForgeResult<HttpResponse> {
+        let mut req = reqwest::blocking::Request::new(request.method, request.url);
+        *req.headers_mut() = request.headers;
+        if let Some(body) = request.body {
+            *req.body_mut() = Some(body.into());
+        }
+
+        let resp = self.http_client.execute(req)?;
+        let status = resp.status();
+        let headers = resp.headers().clone();
+        let body = resp.bytes()?.to_vec();
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+/// 基于`reqwest`异步客户端的默认传输层
+#[cfg(feature = "async")]
+#[derive(Debug, Clone)]
+pub struct AsyncReqwestTransport {
+    http_client: reqwest::Client,
+}
+
+#[cfg(feature = "async")]
+impl AsyncReqwestTransport {
+    pub fn new() -> CurseForgeResult<Self> {
+        Self::with_options(&HttpOptions::default())
+    }
+
+    pub(crate) fn with_options(options: &HttpOptions) -> CurseForgeResult<Self> {
+        Ok(Self::from_client(options.async_client()?))
+    }
+
+    pub fn from_client(http_client: reqwest::Client) -> Self {
+        Self { http_client }
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncTransport for AsyncReqwestTransport {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let mut req = reqwest::Request::new(request.method, request.url);
+            *req.headers_mut() = request.headers;
+            if let Some(body) = request.body {
+                *req.body_mut() = Some(body.into());
+            }
+
+            let resp = self.http_client.execute(req).await?;
+            let status = resp.status();
+            let headers = resp.headers().clone();
+            let body = resp.bytes().await?.to_vec();
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+/// 返回预设响应的内存传输层，用于测试
+///
+/// 按添加顺序匹配请求方法和路径；预设的路径带有查询参数时查询参数也必须一致。
+/// 同一请求匹配多个预设响应时依次返回，最后一个会一直保留。
+/// 没有匹配的预设响应时返回[`Error::Transport`]。
+#[derive(Debug, Clone, Default)]
+pub struct FixtureTransport {
+    fixtures: Arc<Mutex<Vec<Fixture>>>,
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+#[derive(Debug)]
+struct Fixture {
+    method: Method,
+    path: String,
+    response: HttpResponse,
+}
+
+impl Fixture {
+    fn matches(&self, request: &HttpRequest) -> bool {
+        if self.method != request.method {
+            return false;
+        }
+        match self.path.split_once('?') {
+            Some((path, query)) => {
+                request.url.path() == path && request.url.query().unwrap_or_default() == query
+            }
+            None => request.url.path() == self.path,
+        }
+    }
+}
+
+impl FixtureTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加预设响应，`path`可以带查询参数，如`/v1/mods/search?gameId=432`
+    pub fn response(self, method: Method, path: &str, response: HttpResponse) -> Self {
+        self.lock_fixtures().push(Fixture {
+            method,
+            path: path.to_string(),
+            response,
+        });
+        self
+    }
+
+    /// 添加GET请求的JSON响应
+    pub fn json(self, path: &str, status: u16, body: &str) -> Self {
+        let status = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = HttpResponse::new(status, body);
+        response.headers.insert(
+            reqwest::header::CONTENT_TYPE,
+            reqwest::header::HeaderValue::from_static("application/json"),
+        );
+        self.response(Method::GET, path, response)
+    }
+
+    /// 已收到的请求
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn lock_fixtures(&self) -> std::sync::MutexGuard<'_, Vec<Fixture>> {
+        self.fixtures.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn respond(&self, request: HttpRequest) -> CurseForgeResult<HttpResponse> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(request.clone());
+
+        let mut fixtures = self.lock_fixtures();
+        let matched: Vec<usize> = (0..fixtures.len())
+            .filter(|&i| fixtures[i].matches(&request))
+            .collect();
+        match matched.as_slice() {
+            [] => Err(Error::Transport(
+                format!("no fixture for {} {}", request.method, request.url).into(),
+            )),
+            [only] => Ok(fixtures[*only].response.clone()),
+            [first, ..] => Ok(fixtures.remove(*first).response),
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Transport for FixtureTransport {
+    fn send(&self, request: HttpRequest) -> CurseForgeResult<HttpResponse> {
+        self.respond(request)
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncTransport for FixtureTransport {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        Box::pin(std::future::ready(self.respond(request)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_redacts_api_key() {
+        let url = Url::parse("http://localhost/v1/games").unwrap();
+        let request = api_request(Method::GET, url, "secret-key").unwrap();
+        assert!(!format!("{:?}", request).contains("secret-key"));
+        assert_eq!(request.headers.get("x-api-key").unwrap(), "secret-key");
+    }
+
+    #[test]
+    fn test_fixture_transport_sequence() {
+        let transport = FixtureTransport::new()
+            .json("/v1/games/432", 503, "")
+            .json("/v1/games/432", 200, "{}")
+            .json("/v1/mods/search?gameId=432", 200, "[]");
+
+        let get = |url: &str| {
+            let request = HttpRequest::new(Method::GET, Url::parse(url).unwrap());
+            transport.respond(request).map(|resp| resp.status.as_u16())
+        };
+        assert_eq!(get("http://localhost/v1/games/432").unwrap(), 503);
+        assert_eq!(get("http://localhost/v1/games/432").unwrap(), 200);
+        assert_eq!(get("http://localhost/v1/games/432").unwrap(), 200);
+        assert_eq!(
+            get("http://localhost/v1/mods/search?gameId=432").unwrap(),
+            200
+        );
+        assert!(get("http://localhost/v1/mods/search?gameId=1").is_err());
+        assert_eq!(transport.requests().len(), 5);
+    }
+}
@@ -1,5 +1,128 @@
-pub struct Error {
-    //
+use std::fmt;
+
+/// 错误响应体保留的最大长度（字节）
+const MAX_BODY_LENGTH: usize = 512;
+
+/// 错误
+#[derive(Debug)]
+pub enum Error {
+    /// 网络传输错误（连接失败、超时等）
+    Transport(Box<dyn std::error::Error + Send + Sync>),
+
+    /// 服务器返回非2xx状态码
+    Status {
+        /// HTTP状态码
+        status: u16,
+
+        /// 响应体（已截断）
+        body: String,
+    },
+
+    /// JSON解析错误
+    Decode {
+        /// 出错字段的路径
+        path: String,
+
+        /// 原始错误
+        source: serde_json::Error,
+    },
+
+    /// 缺少配置
+    Config(String),
+
+    /// 离线模式下没有该URL的缓存
+    Offline(String),
+}
+
+impl Error {
+    pub(crate) fn status(status: u16, body: &[u8]) -> Self {
+        let body = String::from_utf8_lossy(body);
+        let body = if body.len() > MAX_BODY_LENGTH {
+            let mut end = MAX_BODY_LENGTH;
+            while !body.is_char_boundary(end) {
+                end -= 1;
+            }
+            format!("{}...", &body[..end])
+        } else {
+            body.into_owned()
+        };
+        Error::Status { status, body }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Transport(e) => write!(f, "transport error: {}", e),
+            Error::Status { status, body } => write!(f, "HTTP status {}: {}", status, body),
+            Error::Decode { path, source } => {
+                write!(f, "failed to decode response at `{}`: {}", path, source)
+            }
+            Error::Config(message) => write!(f, "configuration error: {}", message),
+            Error::Offline(url) => write!(f, "offline: no cached response for {}", url),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Transport(e) => Some(e.as_ref()),
+            Error::Decode { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Transport(Box::new(e))
+    }
+}
+
+impl From<serde_path_to_error::Error<serde_json::Error>> for Error {
+    fn from(e: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        Error::Decode {
+            path: e.path().to_string(),
+            source: e.into_inner(),
+        }
+    }
 }
 
 pub type CurseForgeResult<T> = Result<T, Error>;
+
+/// 解析JSON响应体
+pub(crate) fn decode<T: serde::de::DeserializeOwned>(body: &[u8]) -> CurseForgeResult<T> {
+    let deserializer = &mut serde_json::Deserializer::from_slice(body);
+    Ok(serde_path_to_error::deserialize(deserializer)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curseforge::schemas::{DataResponse, Game};
+
+    #[test]
+    fn test_status_body_truncated() {
+        let body = "错".repeat(MAX_BODY_LENGTH);
+        match Error::status(503, body.as_bytes()) {
+            Error::Status { status, body } => {
+                assert_eq!(status, 503);
+                assert!(body.len() <= MAX_BODY_LENGTH + 3);
+                assert!(body.ends_with("..."));
+            }
+            e => panic!("unexpected error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_decode_error_path() {
+        let json = r#"{"data": {"id": "432", "name": "Minecraft"}}"#;
+        let result: CurseForgeResult<DataResponse<Game>> = decode(json.as_bytes());
+        match result {
+            Err(Error::Decode { path, .. }) => assert_eq!(path, "data.id"),
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => panic!("decode should fail"),
+        }
+    }
+}
@@ -0,0 +1,933 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde_json::{Value, json};
+
+use crate::curseforge::error::{CurseForgeResult, Error};
+
+/// 单页最大数量
+const MAX_PAGE_SIZE: u64 = 50;
+
+/// `index + pageSize`的上限，与CurseForge一致
+const MAX_RESULT_WINDOW: u64 = 10000;
+
+/// 模拟服务器返回的数据
+///
+/// 数据格式与[`schemas`](crate::curseforge::schemas)中的类型一致（camelCase JSON）。
+/// 从目录加载时读取以下文件，不存在的文件视为空：
+///
+/// - `games.json`：[`Game`](crate::Game)数组
+/// - `categories.json`：[`Category`](crate::Category)数组
+/// - `mods.json`：[`Mod`](crate::Mod)数组
+/// - `files.json`：[`File`](crate::File)数组
+/// - `versions.json`：以游戏编号为键的[`GameVersionsByType`](crate::curseforge::schemas::GameVersionsByType)数组
+/// - `versions-v2.json`：以游戏编号为键的[`GameVersionsByTypeV2`](crate::curseforge::schemas::GameVersionsByTypeV2)数组
+/// - `version-types.json`：[`GameVersionType`](crate::curseforge::schemas::GameVersionType)数组
+/// - `descriptions.json`：以模组编号为键的模组描述（HTML字符串）
+/// - `changelogs.json`：以文件编号为键的更新日志（HTML字符串）
+/// - `minecraft-versions.json`：[`MinecraftGameVersion`](crate::curseforge::schemas::MinecraftGameVersion)数组
+/// - `minecraft-modloaders.json`：[`MinecraftModLoaderVersion`](crate::curseforge::schemas::MinecraftModLoaderVersion)数组
+#[derive(Debug, Clone, Default)]
+pub struct MockData {
+    games: Vec<Value>,
+    categories: Vec<Value>,
+    mods: Vec<Value>,
+    files: Vec<Value>,
+    versions: HashMap<u64, Value>,
+    versions_v2: HashMap<u64, Value>,
+    version_types: Vec<Value>,
+    descriptions: HashMap<u64, String>,
+    changelogs: HashMap<u64, String>,
+    minecraft_versions: Vec<Value>,
+    minecraft_mod_loaders: Vec<Value>,
+}
+
+/// 错误响应的状态码和响应体
+pub(crate) type Reject = (u16, String);
+
+impl MockData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 从目录加载数据
+    pub fn load(directory: impl AsRef<Path>) -> CurseForgeResult<Self> {
+        let directory = directory.as_ref();
+        Ok(Self {
+            games: read(directory, "games.json")?,
+            categories: read(directory, "categories.json")?,
+            mods: read(directory, "mods.json")?,
+            files: read(directory, "files.json")?,
+            versions: read_by_id(directory, "versions.json")?,
+            versions_v2: read_by_id(directory, "versions-v2.json")?,
+            version_types: read(directory, "version-types.json")?,
+            descriptions: read_by_id(directory, "descriptions.json")?,
+            changelogs: read_by_id(directory, "changelogs.json")?,
+            minecraft_versions: read(directory, "minecraft-versions.json")?,
+            minecraft_mod_loaders: read(directory, "minecraft-modloaders.json")?,
+        })
+    }
+
+    pub fn games(mut self, games: Vec<Value>) -> Self {
+        self.games = games;
+        self
+    }
+
+    pub fn categories(mut self, categories: Vec<Value>) -> Self {
+        self.categories = categories;
+        self
+    }
+
+    pub fn mods(mut self, mods: Vec<Value>) -> Self {
+        self.mods = mods;
+        self
+    }
+
+    pub fn files(mut self, files: Vec<Value>) -> Self {
+        self.files = files;
+        self
+    }
+
+    /// 指定游戏的版本列表
+    pub fn versions(mut self, game_id: u32, versions: Value) -> Self {
+        self.versions.insert(u64::from(game_id), versions);
+        self
+    }
+
+    /// 指定游戏的版本列表（v2）
+    pub fn versions_v2(mut self, game_id: u32, versions: Value) -> Self {
+        self.versions_v2.insert(u64::from(game_id), versions);
+        self
+    }
+
+    pub fn version_types(mut self, version_types: Vec<Value>) -> Self {
+        self.version_types = version_types;
+        self
+    }
+
+    /// 指定模组的描述
+    pub fn description(mut self, mod_id: u32, description: &str) -> Self {
+        self.descriptions
+            .insert(u64::from(mod_id), description.to_string());
+        self
+    }
+
+    /// 指定文件的更新日志
+    pub fn changelog(mut self, file_id: u32, changelog: &str) -> Self {
+        self.changelogs
+            .insert(u64::from(file_id), changelog.to_string());
+        self
+    }
+
+    pub fn minecraft_versions(mut self, minecraft_versions: Vec<Value>) -> Self {
+        self.minecraft_versions = minecraft_versions;
+        self
+    }
+
+    /// 模组加载器详情，列表接口由详情生成
+    pub fn minecraft_mod_loaders(mut self, minecraft_mod_loaders: Vec<Value>) -> Self {
+        self.minecraft_mod_loaders = minecraft_mod_loaders;
+        self
+    }
+
+    /// 处理请求，返回响应体或错误
+    pub(crate) fn route(
+        &self,
+        method: &str,
+        path: &str,
+        query: &Query,
+        body: &[u8],
+    ) -> Result<Value, Reject> {
+        let segments = path
+            .trim_matches('/')
+            .split('/')
+            .map(percent_decode)
+            .collect::<Result<Vec<String>, Reject>>()?;
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        match (method, segments.as_slice()) {
+            ("GET", ["v1", "games"]) => paginate(self.games.iter().collect(), query),
+            ("GET", ["v1", "games", game_id]) => {
+                let game_id = parse_id(game_id)?;
+                let game = find(&self.games, |game| id(game) == Some(game_id))?;
+                Ok(json!({ "data": game }))
+            }
+            ("GET", ["v1", "games", game_id, "versions"]) => {
+                let game_id = parse_id(game_id)?;
+                find(&self.games, |game| id(game) == Some(game_id))?;
+                let versions = self.versions.get(&game_id).cloned();
+                Ok(json!({ "data": versions.unwrap_or_else(|| json!([])) }))
+            }
+            ("GET", ["v2", "games", game_id, "versions"]) => {
+                let game_id = parse_id(game_id)?;
+                find(&self.games, |game| id(game) == Some(game_id))?;
+                let versions = self.versions_v2.get(&game_id).cloned();
+                Ok(json!({ "data": versions.unwrap_or_else(|| json!([])) }))
+            }
+            ("GET", ["v1", "games", game_id, "version-types"]) => {
+                let game_id = parse_id(game_id)?;
+                find(&self.games, |game| id(game) == Some(game_id))?;
+                let version_types = filter(&self.version_types, |version_type| {
+                    u64_field(version_type, "gameId") == Some(game_id)
+                });
+                Ok(json!({ "data": version_types }))
+            }
+            ("GET", ["v1", "categories"]) => self.get_categories(query),
+            ("GET", ["v1", "mods", "search"]) => self.search_mods(query),
+            ("POST", ["v1", "mods"]) => {
+                let mod_ids = ids_from_body(body, "modIds")?;
+                let mods = filter(&self.mods, |mod_| {
+                    id(mod_).is_some_and(|id| mod_ids.contains(&id))
+                });
+                Ok(json!({ "data": mods }))
+            }
+            ("POST", ["v1", "mods", "featured"]) => self.get_featured_mods(body),
+            ("POST", ["v1", "fingerprints"]) => self.get_fingerprint_matches(None, body),
+            ("POST", ["v1", "fingerprints", "fuzzy"]) => self.get_fuzzy_matches(None, body),
+            ("POST", ["v1", "fingerprints", "fuzzy", game_id]) => {
+                self.get_fuzzy_matches(Some(parse_id(game_id)?), body)
+            }
+            ("POST", ["v1", "fingerprints", game_id]) => {
+                self.get_fingerprint_matches(Some(parse_id(game_id)?), body)
+            }
+            ("POST", ["v1", "mods", "files"]) => {
+                let file_ids = ids_from_body(body, "fileIds")?;
+                let files = filter(&self.files, |file| {
+                    id(file).is_some_and(|id| file_ids.contains(&id))
+                });
+                Ok(json!({ "data": files }))
+            }
+            ("GET", ["v1", "mods", mod_id]) => {
+                let mod_id = parse_id(mod_id)?;
+                let mod_ = find(&self.mods, |mod_| id(mod_) == Some(mod_id))?;
+                Ok(json!({ "data": mod_ }))
+            }
+            ("GET", ["v1", "mods", mod_id, "description"]) => {
+                let mod_id = parse_id(mod_id)?;
+                find(&self.mods, |mod_| id(mod_) == Some(mod_id))?;
+                let description = self.descriptions.get(&mod_id).cloned();
+                Ok(json!({ "data": description.unwrap_or_default() }))
+            }
+            ("GET", ["v1", "mods", mod_id, "files"]) => self.get_mod_files(mod_id, query),
+            ("GET", ["v1", "mods", mod_id, "files", file_id]) => {
+                let mod_id = parse_id(mod_id)?;
+                let file_id = parse_id(file_id)?;
+                let file = find(&self.files, |file| {
+                    id(file) == Some(file_id) && u64_field(file, "modId") == Some(mod_id)
+                })?;
+                Ok(json!({ "data": file }))
+            }
+            ("GET", ["v1", "mods", mod_id, "files", file_id, "download-url"]) => {
+                let mod_id = parse_id(mod_id)?;
+                let file_id = parse_id(file_id)?;
+                let file = find(&self.files, |file| {
+                    id(file) == Some(file_id) && u64_field(file, "modId") == Some(mod_id)
+                })?;
+                Ok(json!({ "data": file.get("downloadUrl").cloned().unwrap_or(Value::Null) }))
+            }
+            ("GET", ["v1", "mods", mod_id, "files", file_id, "changelog"]) => {
+                let mod_id = parse_id(mod_id)?;
+                let file_id = parse_id(file_id)?;
+                find(&self.files, |file| {
+                    id(file) == Some(file_id) && u64_field(file, "modId") == Some(mod_id)
+                })?;
+                let changelog = self.changelogs.get(&file_id).cloned();
+                Ok(json!({ "data": changelog.unwrap_or_default() }))
+            }
+            ("GET", ["v1", "minecraft", "version"]) => self.get_minecraft_versions(query),
+            ("GET", ["v1", "minecraft", "version", game_version]) => {
+                let version = find(&self.minecraft_versions, |version| {
+                    str_field(version, "versionString") == Some(*game_version)
+                })?;
+                Ok(json!({ "data": version }))
+            }
+            ("GET", ["v1", "minecraft", "modloader"]) => self.get_minecraft_mod_loaders(query),
+            ("GET", ["v1", "minecraft", "modloader", name]) => {
+                let mod_loader = find(&self.minecraft_mod_loaders, |mod_loader| {
+                    str_field(mod_loader, "name") == Some(*name)
+                })?;
+                Ok(json!({ "data": mod_loader }))
+            }
+            _ => Err((404, String::new())),
+        }
+    }
+
+    fn get_minecraft_versions(&self, query: &Query) -> Result<Value, Reject> {
+        let mut versions: Vec<&Value> = self.minecraft_versions.iter().collect();
+        versions.sort_by_key(|version| id(version));
+        if query.bool("sortDescending")?.unwrap_or(false) {
+            versions.reverse();
+        }
+        Ok(json!({ "data": versions }))
+    }
+
+    fn get_minecraft_mod_loaders(&self, query: &Query) -> Result<Value, Reject> {
+        let version = query.get("version");
+        let include_all = query.bool("includeAll")?.unwrap_or(false);
+        let mod_loaders: Vec<Value> = self
+            .minecraft_mod_loaders
+            .iter()
+            .filter(|mod_loader| {
+                version.is_none() || str_field(mod_loader, "minecraftVersion") == version
+            })
+            .filter(|mod_loader| {
+                include_all
+                    || mod_loader.get("latest") == Some(&Value::Bool(true))
+                    || mod_loader.get("recommended") == Some(&Value::Bool(true))
+            })
+            .map(|mod_loader| {
+                json!({
+                    "name": mod_loader.get("name"),
+                    "gameVersion": mod_loader.get("minecraftVersion"),
+                    "latest": mod_loader.get("latest"),
+                    "recommended": mod_loader.get("recommended"),
+                    "dateModified": mod_loader.get("dateModified"),
+                    "type": mod_loader.get("type"),
+                })
+            })
+            .collect();
+        Ok(json!({ "data": mod_loaders }))
+    }
+
+    fn get_categories(&self, query: &Query) -> Result<Value, Reject> {
+        let game_id = query.required_u64("gameId")?;
+        let class_id = query.u64("classId")?;
+        let classes_only = query.bool("classesOnly")?.unwrap_or(false);
+        let categories = filter(&self.categories, |category| {
+            u64_field(category, "gameId") == Some(game_id)
+                && (class_id.is_none() || u64_field(category, "classId") == class_id)
+                && (!classes_only || category.get("isClass") == Some(&Value::Bool(true)))
+        });
+        Ok(json!({ "data": categories }))
+    }
+
+    fn search_mods(&self, query: &Query) -> Result<Value, Reject> {
+        let game_id = query.required_u64("gameId")?;
+        let class_id = query.u64("classId")?;
+        let category_id = query.u64("categoryId")?;
+        let game_version = query.get("gameVersion");
+        let mod_loader_type = query.u64("modLoaderType")?;
+        let game_version_type_id = query.u64("gameVersionTypeId")?;
+        let search_filter = query.get("searchFilter").map(str::to_lowercase);
+        let slug = query.get("slug");
+
+        let mut mods: Vec<&Value> = self
+            .mods
+            .iter()
+            .filter(|mod_| u64_field(mod_, "gameId") == Some(game_id))
+            .filter(|mod_| class_id.is_none() || u64_field(mod_, "classId") == class_id)
+            .filter(|mod_| {
+                category_id.is_none()
+                    || array(mod_, "categories")
+                        .any(|category| u64_field(category, "id") == category_id)
+            })
+            .filter(|mod_| {
+                if game_version.is_none()
+                    && mod_loader_type.is_none()
+                    && game_version_type_id.is_none()
+                {
+                    return true;
+                }
+                array(mod_, "latestFilesIndexes").any(|index| {
+                    (game_version.is_none() || str_field(index, "gameVersion") == game_version)
+                        && (mod_loader_type.is_none()
+                            || u64_field(index, "modLoader") == mod_loader_type)
+                        && (game_version_type_id.is_none()
+                            || u64_field(index, "gameVersionTypeId") == game_version_type_id)
+                })
+            })
+            .filter(|mod_| slug.is_none() || str_field(mod_, "slug") == slug)
+            .filter(|mod_| match &search_filter {
+                Some(search_filter) => matches_search(mod_, search_filter),
+                None => true,
+            })
+            .collect();
+
+        if let Some(sort_field) = query.u64("sortField")? {
+            sort_mods(&mut mods, sort_field);
+            if query.get("sortOrder") != Some("asc") {
+                mods.reverse();
+            }
+        }
+        paginate(mods, query)
+    }
+
+    /// 推荐为`isFeatured`为真的模组，热门按下载量，最近更新按修改时间，各取前6个
+    fn get_featured_mods(&self, body: &[u8]) -> Result<Value, Reject> {
+        let body: Value =
+            serde_json::from_slice(body).map_err(|e| (400, format!("invalid body: {}", e)))?;
+        let game_id =
+            u64_field(&body, "gameId").ok_or((400, String::from("gameId is required")))?;
+        let excluded: Vec<u64> = array(&body, "excludedModIds")
+            .filter_map(Value::as_u64)
+            .collect();
+        let mods: Vec<&Value> = self
+            .mods
+            .iter()
+            .filter(|mod_| u64_field(mod_, "gameId") == Some(game_id))
+            .filter(|mod_| id(mod_).is_some_and(|id| !excluded.contains(&id)))
+            .collect();
+
+        let featured: Vec<&Value> = mods
+            .iter()
+            .copied()
+            .filter(|mod_| mod_.get("isFeatured") == Some(&Value::Bool(true)))
+            .take(6)
+            .collect();
+        let mut popular = mods.clone();
+        sort_mods(&mut popular, 2);
+        popular.reverse();
+        popular.truncate(6);
+        let mut recently_updated = mods;
+        sort_mods(&mut recently_updated, 3);
+        recently_updated.reverse();
+        recently_updated.truncate(6);
+        Ok(json!({
+            "data": {
+                "featured": featured,
+                "popular": popular,
+                "recentlyUpdated": recently_updated,
+            },
+        }))
+    }
+
+    /// 以文件的`fileFingerprint`精确匹配
+    fn get_fingerprint_matches(&self, game_id: Option<u64>, body: &[u8]) -> Result<Value, Reject> {
+        let fingerprints = ids_from_body(body, "fingerprints")?;
+        let mut exact_matches = vec![];
+        let mut exact_fingerprints = vec![];
+        for file in &self.files {
+            let Some(fingerprint) = u64_field(file, "fileFingerprint") else {
+                continue;
+            };
+            if !fingerprints.contains(&fingerprint)
+                || game_id.is_some_and(|game_id| u64_field(file, "gameId") != Some(game_id))
+            {
+                continue;
+            }
+            exact_matches.push(self.fingerprint_match(file));
+            exact_fingerprints.push(fingerprint);
+        }
+        let unmatched_fingerprints: Vec<u64> = fingerprints
+            .iter()
+            .copied()
+            .filter(|fingerprint| !exact_fingerprints.contains(fingerprint))
+            .collect();
+        Ok(json!({
+            "data": {
+                "isCacheBuilt": true,
+                "exactMatches": exact_matches,
+                "exactFingerprints": exact_fingerprints,
+                "partialMatches": [],
+                "partialMatchFingerprints": {},
+                "installedFingerprints": fingerprints,
+                "unmatchedFingerprints": unmatched_fingerprints,
+            },
+        }))
+    }
+
+    /// 以文件`modules`中的指纹模糊匹配，任一指纹相同即视为匹配
+    fn get_fuzzy_matches(&self, game_id: Option<u64>, body: &[u8]) -> Result<Value, Reject> {
+        let body: Value =
+            serde_json::from_slice(body).map_err(|e| (400, format!("invalid body: {}", e)))?;
+        let game_id = game_id
+            .or_else(|| u64_field(&body, "gameId"))
+            .ok_or((400, String::from("gameId is required")))?;
+        let folder_fingerprints: Vec<u64> = array(&body, "fingerprints")
+            .flat_map(|folder| array(folder, "fingerprints"))
+            .filter_map(Value::as_u64)
+            .collect();
+
+        let mut fuzzy_matches = vec![];
+        for file in &self.files {
+            if u64_field(file, "gameId") != Some(game_id) {
+                continue;
+            }
+            let fingerprints: Vec<u64> = array(file, "modules")
+                .filter_map(|module| u64_field(module, "fingerprint"))
+                .filter(|fingerprint| folder_fingerprints.contains(fingerprint))
+                .collect();
+            if fingerprints.is_empty() {
+                continue;
+            }
+            let mut fuzzy_match = self.fingerprint_match(file);
+            fuzzy_match["fingerprints"] = json!(fingerprints);
+            fuzzy_matches.push(fuzzy_match);
+        }
+        Ok(json!({ "data": { "fuzzyMatches": fuzzy_matches } }))
+    }
+
+    /// 匹配的文件及其所属模组的全部文件
+    fn fingerprint_match(&self, file: &Value) -> Value {
+        let mod_id = u64_field(file, "modId");
+        let latest_files = filter(&self.files, |other| u64_field(other, "modId") == mod_id);
+        json!({ "id": mod_id, "file": file, "latestFiles": latest_files })
+    }
+
+    fn get_mod_files(&self, mod_id: &str, query: &Query) -> Result<Value, Reject> {
+        let mod_id = parse_id(mod_id)?;
+        find(&self.mods, |mod_| id(mod_) == Some(mod_id))?;
+        let game_version = query.get("gameVersion");
+        let mod_loader = match query.u64("modLoaderType")? {
+            Some(mod_loader_type) => mod_loader_name(mod_loader_type),
+            None => None,
+        };
+        let game_version_type_id = query.u64("gameVersionTypeId")?;
+
+        let files: Vec<&Value> = self
+            .files
+            .iter()
+            .filter(|file| u64_field(file, "modId") == Some(mod_id))
+            .filter(|file| match game_version {
+                Some(game_version) => array(file, "gameVersions")
+                    .any(|version| version.as_str() == Some(game_version)),
+                None => true,
+            })
+            .filter(|file| match mod_loader {
+                Some(mod_loader) => array(file, "gameVersions").any(|version| {
+                    version
+                        .as_str()
+                        .is_some_and(|version| version.eq_ignore_ascii_case(mod_loader))
+                }),
+                None => true,
+            })
+            .filter(|file| {
+                game_version_type_id.is_none()
+                    || array(file, "sortableGameVersions").any(|version| {
+                        u64_field(version, "gameVersionTypeId") == game_version_type_id
+                    })
+            })
+            .collect();
+        paginate(files, query)
+    }
+}
+
+/// 解析后的查询参数
+pub(crate) struct Query(Vec<(String, String)>);
+
+impl Query {
+    pub(crate) fn parse(query: &str) -> Result<Self, Reject> {
+        serde_urlencoded::from_str(query)
+            .map(Self)
+            .map_err(|e| (400, format!("invalid query: {}", e)))
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn u64(&self, name: &str) -> Result<Option<u64>, Reject> {
+        match self.get(name) {
+            Some(value) => match value.parse() {
+                Ok(value) => Ok(Some(value)),
+                Err(_) => Err((400, format!("invalid {}: {}", name, value))),
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn required_u64(&self, name: &str) -> Result<u64, Reject> {
+        self.u64(name)?
+            .ok_or_else(|| (400, format!("{} is required", name)))
+    }
+
+    fn bool(&self, name: &str) -> Result<Option<bool>, Reject> {
+        match self.get(name) {
+            Some(value) if value.eq_ignore_ascii_case("true") => Ok(Some(true)),
+            Some(value) if value.eq_ignore_ascii_case("false") => Ok(Some(false)),
+            Some(value) => Err((400, format!("invalid {}: {}", name, value))),
+            None => Ok(None),
+        }
+    }
+}
+
+fn read<T: serde::de::DeserializeOwned + Default>(
+    directory: &Path,
+    name: &str,
+) -> CurseForgeResult<T> {
+    let path = directory.join(name);
+    let content = match std::fs::read(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(T::default()),
+        Err(e) => {
+            return Err(Error::Config(format!(
+                "cannot read {}: {}",
+                path.display(),
+                e
+            )));
+        }
+    };
+    serde_json::from_slice(&content)
+        .map_err(|e| Error::Config(format!("invalid fixture {}: {}", path.display(), e)))
+}
+
+/// 读取以编号为键的JSON对象
+fn read_by_id<T: serde::de::DeserializeOwned>(
+    directory: &Path,
+    name: &str,
+) -> CurseForgeResult<HashMap<u64, T>> {
+    let values: HashMap<String, T> = read(directory, name)?;
+    values
+        .into_iter()
+        .map(|(id, value)| match id.parse() {
+            Ok(id) => Ok((id, value)),
+            Err(_) => Err(Error::Config(format!("invalid id `{}` in {}", id, name))),
+        })
+        .collect()
+}
+
+fn paginate(items: Vec<&Value>, query: &Query) -> Result<Value, Reject> {
+    let index = query.u64("index")?.unwrap_or(0);
+    let page_size = query
+        .u64("pageSize")?
+        .unwrap_or(MAX_PAGE_SIZE)
+        .min(MAX_PAGE_SIZE);
+    let end = index.checked_add(page_size);
+    if end.is_none_or(|end| end > MAX_RESULT_WINDOW) {
+        return Err((
+            400,
+            format!("index + pageSize must not exceed {}", MAX_RESULT_WINDOW),
+        ));
+    }
+
+    let total_count = items.len();
+    let data: Vec<&Value> = items
+        .into_iter()
+        .skip(index as usize)
+        .take(page_size as usize)
+        .collect();
+    Ok(json!({
+        "data": data,
+        "pagination": {
+            "index": index,
+            "pageSize": page_size,
+            "resultCount": data.len(),
+            "totalCount": total_count,
+        },
+    }))
+}
+
+/// 读取请求体中的编号数组
+fn ids_from_body(body: &[u8], field: &str) -> Result<Vec<u64>, Reject> {
+    let body: Value =
+        serde_json::from_slice(body).map_err(|e| (400, format!("invalid body: {}", e)))?;
+    body.get(field)
+        .and_then(Value::as_array)
+        .and_then(|ids| ids.iter().map(Value::as_u64).collect())
+        .ok_or_else(|| (400, format!("{} is required", field)))
+}
+
+/// 解码路径段中的`%XX`，客户端会编码版本号、加载器名称中的特殊字符
+fn percent_decode(segment: &str) -> Result<String, Reject> {
+    let invalid = || (400, format!("invalid path segment `{}`", segment));
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes.get(i + 1..i + 3).ok_or_else(invalid)?;
+            if !hex.iter().all(u8::is_ascii_hexdigit) {
+                return Err(invalid());
+            }
+            let hex = std::str::from_utf8(hex).map_err(|_| invalid())?;
+            decoded.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| invalid())
+}
+
+fn parse_id(id: &str) -> Result<u64, Reject> {
+    id.parse().map_err(|_| (400, format!("invalid id: {}", id)))
+}
+
+fn find(items: &[Value], predicate: impl Fn(&Value) -> bool) -> Result<&Value, Reject> {
+    items
+        .iter()
+        .find(|item| predicate(item))
+        .ok_or((404, String::new()))
+}
+
+fn filter(items: &[Value], predicate: impl Fn(&Value) -> bool) -> Vec<&Value> {
+    items.iter().filter(|item| predicate(item)).collect()
+}
+
+fn id(value: &Value) -> Option<u64> {
+    u64_field(value, "id")
+}
+
+fn u64_field(value: &Value, name: &str) -> Option<u64> {
+    value.get(name)?.as_u64()
+}
+
+fn str_field<'a>(value: &'a Value, name: &str) -> Option<&'a str> {
+    value.get(name)?.as_str()
+}
+
+fn array<'a>(value: &'a Value, name: &str) -> impl Iterator<Item = &'a Value> {
+    value
+        .get(name)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+/// 名称、概要和作者中包含搜索词（不区分大小写）
+fn matches_search(mod_: &Value, search_filter: &str) -> bool {
+    let contains =
+        |text: Option<&str>| text.is_some_and(|text| text.to_lowercase().contains(search_filter));
+    contains(str_field(mod_, "name"))
+        || contains(str_field(mod_, "summary"))
+        || array(mod_, "authors").any(|author| contains(str_field(author, "name")))
+}
+
+/// 按`SortField`升序排序，不支持的字段保持原顺序
+fn sort_mods(mods: &mut [&Value], sort_field: u64) {
+    match sort_field {
+        // Popularity、TotalDownloads
+        2 | 6 => mods.sort_by_key(|mod_| u64_field(mod_, "downloadCount")),
+        // LastUpdated
+        3 => mods.sort_by_key(|mod_| str_field(mod_, "dateModified")),
+        // Name
+        4 => mods.sort_by_key(|mod_| str_field(mod_, "name").map(str::to_lowercase)),
+        // Author
+        5 => mods.sort_by_key(|mod_| {
+            array(mod_, "authors")
+                .next()
+                .and_then(|author| str_field(author, "name"))
+                .map(str::to_lowercase)
+        }),
+        // ReleasedDate
+        11 => mods.sort_by_key(|mod_| str_field(mod_, "dateReleased")),
+        _ => {}
+    }
+}
+
+/// 文件的`gameVersions`中以名称标记加载器
+fn mod_loader_name(mod_loader_type: u64) -> Option<&'static str> {
+    match mod_loader_type {
+        1 => Some("Forge"),
+        2 => Some("Cauldron"),
+        3 => Some("LiteLoader"),
+        4 => Some("Fabric"),
+        5 => Some("Quilt"),
+        6 => Some("NeoForge"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data() -> MockData {
+        MockData::new()
+            .games(vec![json!({"id": 432, "name": "Minecraft"})])
+            .categories(vec![
+                json!({"id": 6, "gameId": 432, "name": "Mods", "isClass": true}),
+                json!({"id": 423, "gameId": 432, "name": "Map", "classId": 6}),
+            ])
+            .mods(vec![
+                json!({
+                    "id": 1, "gameId": 432, "classId": 6, "name": "Alpha", "slug": "alpha",
+                    "downloadCount": 10, "categories": [{"id": 423}],
+                    "latestFilesIndexes": [{"gameVersion": "1.20.1", "modLoader": 1}],
+                }),
+                json!({
+                    "id": 2, "gameId": 432, "classId": 6, "name": "Beta", "slug": "beta",
+                    "downloadCount": 30, "categories": [],
+                    "latestFilesIndexes": [{"gameVersion": "1.20.1", "modLoader": 4}],
+                }),
+                json!({"id": 3, "gameId": 1, "name": "Other", "slug": "other"}),
+            ])
+            .files(vec![
+                json!({
+                    "id": 10, "gameId": 432, "modId": 1, "gameVersions": ["1.20.1", "Forge"],
+                    "fileFingerprint": 1001, "modules": [{"name": "META-INF", "fingerprint": 7}],
+                }),
+                json!({"id": 11, "modId": 1, "gameVersions": ["1.19.2", "Forge"]}),
+            ])
+    }
+
+    fn get(data: &MockData, target: &str) -> Result<Value, Reject> {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        data.route("GET", path, &Query::parse(query)?, b"")
+    }
+
+    fn ids(response: &Value) -> Vec<u64> {
+        response["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["id"].as_u64().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_search_filters() {
+        let data = data();
+        let response = get(&data, "/v1/mods/search?gameId=432").unwrap();
+        assert_eq!(ids(&response), [1, 2]);
+        assert_eq!(response["pagination"]["totalCount"], 2);
+
+        let response = get(&data, "/v1/mods/search?gameId=432&categoryId=423").unwrap();
+        assert_eq!(ids(&response), [1]);
+        let response = get(
+            &data,
+            "/v1/mods/search?gameId=432&gameVersion=1.20.1&modLoaderType=4",
+        )
+        .unwrap();
+        assert_eq!(ids(&response), [2]);
+        let response = get(&data, "/v1/mods/search?gameId=432&searchFilter=BET").unwrap();
+        assert_eq!(ids(&response), [2]);
+        let response = get(&data, "/v1/mods/search?gameId=432&sortField=6").unwrap();
+        assert_eq!(ids(&response), [2, 1]);
+        let response = get(
+            &data,
+            "/v1/mods/search?gameId=432&sortField=6&sortOrder=asc",
+        )
+        .unwrap();
+        assert_eq!(ids(&response), [1, 2]);
+
+        assert_eq!(get(&data, "/v1/mods/search").unwrap_err().0, 400);
+        assert_eq!(get(&data, "/v1/mods/search?gameId=x").unwrap_err().0, 400);
+    }
+
+    #[test]
+    fn test_get_by_ids() {
+        let data = data();
+        let query = Query::parse("").unwrap();
+        let response = data.route("POST", "/v1/mods", &query, br#"{"modIds": [3, 1, 99]}"#);
+        assert_eq!(ids(&response.unwrap()), [1, 3]);
+        let response = data.route("POST", "/v1/mods", &query, b"{}");
+        assert_eq!(response.unwrap_err().0, 400);
+        let response = data.route("POST", "/v1/mods/files", &query, br#"{"fileIds": [11]}"#);
+        assert_eq!(ids(&response.unwrap()), [11]);
+
+        let body = br#"{"fingerprints": [1001, 5]}"#;
+        let response = data
+            .route("POST", "/v1/fingerprints/432", &query, body)
+            .unwrap();
+        assert_eq!(response["data"]["exactMatches"][0]["file"]["id"], 10);
+        assert_eq!(response["data"]["unmatchedFingerprints"], json!([5]));
+        let response = data
+            .route("POST", "/v1/fingerprints/1", &query, body)
+            .unwrap();
+        assert_eq!(response["data"]["exactMatches"], json!([]));
+        let body =
+            br#"{"gameId": 432, "fingerprints": [{"foldername": "a", "fingerprints": [7]}]}"#;
+        let response = data
+            .route("POST", "/v1/fingerprints/fuzzy", &query, body)
+            .unwrap();
+        assert_eq!(
+            response["data"]["fuzzyMatches"][0]["fingerprints"],
+            json!([7])
+        );
+
+        let body = br#"{"gameId": 432, "excludedModIds": [1]}"#;
+        let response = data
+            .route("POST", "/v1/mods/featured", &query, body)
+            .unwrap();
+        assert_eq!(response["data"]["popular"][0]["id"], 2);
+        assert_eq!(response["data"]["popular"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_pagination() {
+        let data = data();
+        let response = get(&data, "/v1/mods/search?gameId=432&index=1&pageSize=1").unwrap();
+        assert_eq!(ids(&response), [2]);
+        assert_eq!(
+            response["pagination"],
+            json!({"index": 1, "pageSize": 1, "resultCount": 1, "totalCount": 2})
+        );
+
+        let response = get(&data, "/v1/mods/search?gameId=432&pageSize=100").unwrap();
+        assert_eq!(response["pagination"]["pageSize"], 50);
+        let result = get(&data, "/v1/mods/search?gameId=432&index=9990&pageSize=50");
+        assert_eq!(result.unwrap_err().0, 400);
+        let result = get(&data, "/v1/games?index=18446744073709551615");
+        assert_eq!(result.unwrap_err().0, 400);
+    }
+
+    #[test]
+    fn test_lookup_routes() {
+        let data = data();
+        assert_eq!(get(&data, "/v1/games/432").unwrap()["data"]["id"], 432);
+        assert_eq!(get(&data, "/v1/games/1").unwrap_err().0, 404);
+        let versions =
+            json!([{"type": 1, "versions": [{"id": 9990, "slug": "1-20-1", "name": "1.20.1"}]}]);
+        let data = data.versions_v2(432, versions.clone());
+        assert_eq!(
+            get(&data, "/v2/games/432/versions").unwrap()["data"],
+            versions
+        );
+        assert_eq!(get(&data, "/v2/games/1/versions").unwrap_err().0, 404);
+        assert_eq!(get(&data, "/v1/mods/2").unwrap()["data"]["slug"], "beta");
+        assert_eq!(get(&data, "/v1/mods/1/files/11").unwrap()["data"]["id"], 11);
+        assert_eq!(get(&data, "/v1/mods/2/files/11").unwrap_err().0, 404);
+        assert_eq!(get(&data, "/v1/mods/1/description").unwrap()["data"], "");
+        let data = data.changelog(11, "<p>Fix</p>");
+        let response = get(&data, "/v1/mods/1/files/11/changelog").unwrap();
+        assert_eq!(response["data"], "<p>Fix</p>");
+        assert_eq!(get(&data, "/v1/unknown").unwrap_err().0, 404);
+
+        let response = get(&data, "/v1/mods/1/files?gameVersion=1.19.2").unwrap();
+        assert_eq!(ids(&response), [11]);
+        let response = get(&data, "/v1/mods/1/files?modLoaderType=4").unwrap();
+        assert_eq!(ids(&response), Vec::<u64>::new());
+
+        let response = get(&data, "/v1/categories?gameId=432&classesOnly=true").unwrap();
+        assert_eq!(ids(&response), [6]);
+        let response = get(&data, "/v1/categories?gameId=432&classId=6").unwrap();
+        assert_eq!(ids(&response), [423]);
+    }
+
+    #[test]
+    fn test_minecraft_routes() {
+        let data = MockData::new()
+            .minecraft_versions(vec![
+                json!({"id": 2, "versionString": "1.20.1"}),
+                json!({"id": 1, "versionString": "1.19.2"}),
+                json!({"id": 3, "versionString": "1.21 Pre-Release 1"}),
+            ])
+            .minecraft_mod_loaders(vec![
+                json!({"name": "forge-47.2.0", "minecraftVersion": "1.20.1", "latest": false,
+                    "recommended": true, "type": 1}),
+                json!({"name": "forge-47.1.0", "minecraftVersion": "1.20.1", "latest": false,
+                    "recommended": false, "type": 1}),
+                json!({"name": "forge-43.3.0", "minecraftVersion": "1.19.2", "latest": true,
+                    "recommended": false, "type": 1}),
+            ]);
+        let response = get(&data, "/v1/minecraft/version").unwrap();
+        assert_eq!(ids(&response), [1, 2, 3]);
+        let response = get(&data, "/v1/minecraft/version?sortDescending=true").unwrap();
+        assert_eq!(ids(&response), [3, 2, 1]);
+        let response = get(&data, "/v1/minecraft/version/1.19.2").unwrap();
+        assert_eq!(response["data"]["id"], 1);
+        let response = get(&data, "/v1/minecraft/version/1.21%20Pre-Release%201").unwrap();
+        assert_eq!(response["data"]["id"], 3);
+        assert_eq!(get(&data, "/v1/minecraft/version/1.0").unwrap_err().0, 404);
+        assert_eq!(get(&data, "/v1/minecraft/version/1%2").unwrap_err().0, 400);
+        assert_eq!(get(&data, "/v1/minecraft/version/%zz").unwrap_err().0, 400);
+        assert_eq!(get(&data, "/v1/minecraft/version/%FF").unwrap_err().0, 400);
+
+        let response = get(&data, "/v1/minecraft/modloader?version=1.20.1").unwrap();
+        assert_eq!(
+            response["data"],
+            json!([{"name": "forge-47.2.0", "gameVersion": "1.20.1",
+            "latest": false, "recommended": true, "dateModified": null, "type": 1}])
+        );
+        let response = get(&data, "/v1/minecraft/modloader?includeAll=true").unwrap();
+        assert_eq!(response["data"].as_array().unwrap().len(), 3);
+        let response = get(&data, "/v1/minecraft/modloader/forge-47.1.0").unwrap();
+        assert_eq!(response["data"]["minecraftVersion"], "1.20.1");
+        let response = get(&data, "/v1/minecraft/modloader/forge%2D47%2e1.0").unwrap();
+        assert_eq!(response["data"]["minecraftVersion"], "1.20.1");
+    }
+}
@@ -0,0 +1,223 @@
+//! 用于集成测试的本地CurseForge模拟服务器
+
+mod data;
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+pub use data::MockData;
+use data::{Query, Reject};
+
+/// 请求体大小上限
+const MAX_BODY_SIZE: usize = 1 << 20;
+
+/// 本地CurseForge模拟服务器
+///
+/// 在后台线程中处理请求，未携带`x-api-key`请求头时返回403。
+/// 服务器在[`MockServer`]被丢弃时停止。
+///
+/// ```no_run
+/// use curseforge_api::{Client, MockData, MockServer};
+///
+/// let server = MockServer::start(MockData::load("tests/fixtures")?)?;
+/// let client = Client::builder()
+///     .base_url(&server.base_url())
+///     .api_key("test")
+///     .build()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct MockServer {
+    address: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// 在`127.0.0.1`的随机端口上启动
+    pub fn start(data: MockData) -> io::Result<Self> {
+        Self::bind("127.0.0.1:0", data)
+    }
+
+    /// 在指定地址上启动
+    pub fn bind(address: impl ToSocketAddrs, data: MockData) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let data = Arc::new(data);
+
+        let stopped = shutdown.clone();
+        let handle = std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let data = data.clone();
+                std::thread::spawn(move || {
+                    if let Err(e) = handle_connection(stream, &data) {
+                        tracing::debug!(error = %e, "mock server connection failed");
+                    }
+                });
+            }
+        });
+
+        Ok(Self {
+            address,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// 用于[`ClientBuilder::base_url`](crate::ClientBuilder::base_url)的地址
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// 阻塞当前线程直到服务器停止
+    pub fn join(mut self) {
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        let Some(handle) = self.handle.take() else {
+            return;
+        };
+        self.shutdown.store(true, Ordering::SeqCst);
+        // 唤醒阻塞在accept上的线程
+        let _ = TcpStream::connect(self.address);
+        let _ = handle.join();
+    }
+}
+
+/// 读取一个HTTP/1.1请求并返回响应，响应后关闭连接
+fn handle_connection(stream: TcpStream, data: &MockData) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line)? == 0 {
+        return Ok(());
+    }
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return write_response(stream, (400, String::from("malformed request line")));
+    };
+
+    let mut api_key = None;
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("x-api-key") {
+                api_key = Some(value.to_string());
+            } else if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse().unwrap_or(0);
+            }
+        }
+    }
+    if content_length > MAX_BODY_SIZE {
+        return write_response(stream, (413, String::new()));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let result = match api_key {
+        Some(api_key) if !api_key.is_empty() => {
+            Query::parse(query).and_then(|query| data.route(method, path, &query, &body))
+        }
+        _ => Err((403, String::new())),
+    };
+    tracing::debug!(method, target, "mock server request");
+    match result {
+        Ok(value) => write_response(stream, (200, value.to_string())),
+        Err(reject) => write_response(stream, reject),
+    }
+}
+
+fn write_response(mut stream: TcpStream, (status, body): Reject) -> io::Result<()> {
+    let reason = StatusCode::from_u16(status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or_default();
+    let content_type = if status == 200 {
+        "application/json; charset=utf-8"
+    } else {
+        "text/plain; charset=utf-8"
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        reason,
+        content_type,
+        body.len()
+    )?;
+    stream.write_all(body.as_bytes())?;
+    stream.flush()
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod tests {
+    use super::*;
+    use crate::curseforge::api::{GamesApi, ModsApi};
+    use crate::curseforge::schemas::SearchModsParameters;
+    use crate::{Client, error::Error};
+    use serde_json::json;
+
+    #[test]
+    fn test_client_end_to_end() {
+        let data = MockData::new().games(vec![json!({
+            "id": 432,
+            "name": "Minecraft",
+            "slug": "minecraft",
+            "dateModified": "2024-08-29T09:54:14.45Z",
+            "assets": {"iconUrl": "", "tileUrl": "", "coverUrl": ""},
+            "status": 6,
+            "apiStatus": 2
+        })]);
+        let server = MockServer::start(data).unwrap();
+        let client = Client::new(&server.base_url(), "test").unwrap();
+
+        assert_eq!(client.get_game(432).unwrap().data.name, "Minecraft");
+        let games = client.get_games(None, Some(10)).unwrap();
+        assert_eq!(games.pagination.total_count, 1);
+        assert!(matches!(
+            client.get_game(1),
+            Err(Error::Status { status: 404, .. })
+        ));
+        let result = client.search_mods(SearchModsParameters::new(432));
+        assert_eq!(result.unwrap().data.len(), 0);
+
+        let client = Client::new(&server.base_url(), "").unwrap();
+        assert!(matches!(
+            client.get_game(432),
+            Err(Error::Status { status: 403, .. })
+        ));
+    }
+}
@@ -1,4 +1,6 @@
 pub mod api;
 pub mod client;
 pub mod error;
+#[cfg(feature = "mock-server")]
+pub mod mock_server;
 pub mod schemas;
@@ -5,64 +5,155 @@ use std::collections::HashMap;
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct File {
-    id: u32,
-    game_id: u32,
-    mod_id: u32,
-    // is_available: bool,
-    display_name: String,
-    file_name: String,
-    release_type: FileReleaseType,
-    file_status: FileStatus,
-    hashes: Vec<FileHash>,
-    file_date: String,
-    file_length: u64,
-    download_count: u64,
-    file_size_on_disk: Option<u64>,
-    download_url: String,
-    game_versions: Vec<String>,
-    // sortableGameVersions
-    dependencies: Vec<FileDependency>,
-    // exposeAsAlternative
-    // parentProjectFileId	integer(int32)¦null	none
-    // alternateFileId	integer(int32)¦null	none
-    // isServerPack	boolean¦null	none
-    // serverPackFileId	integer(int32)¦null	none
-    // isEarlyAccessContent	boolean¦null	none
-    // earlyAccessEndDate	string(date-time)¦null	none
-    // fileFingerprint	integer(int64)	none
-    // modules	[FileModule]	nSelf::one
+    /// 编号
+    pub id: u32,
+
+    /// 游戏编号
+    pub game_id: u32,
+
+    /// 模组编号
+    pub mod_id: u32,
+
+    /// 是否可用
+    pub is_available: bool,
+
+    /// 显示名称
+    pub display_name: String,
+
+    /// 文件名
+    pub file_name: String,
+
+    /// 发布类型
+    pub release_type: FileReleaseType,
+
+    /// 状态
+    pub file_status: FileStatus,
+
+    /// 哈希值
+    pub hashes: Vec<FileHash>,
+
+    /// 发布时间
+    pub file_date: String,
+
+    /// 文件大小
+    pub file_length: u64,
+
+    /// 下载量
+    pub download_count: u64,
+
+    /// 解压后的大小
+    pub file_size_on_disk: Option<u64>,
+
+    /// 作者不允许第三方分发时为空
+    pub download_url: Option<String>,
+
+    /// 支持的游戏版本和加载器名称
+    pub game_versions: Vec<String>,
+
+    /// 支持的游戏版本，可用于排序
+    pub sortable_game_versions: Vec<SortableGameVersion>,
+
+    /// 依赖
+    pub dependencies: Vec<FileDependency>,
+
+    /// 是否作为其他文件的替代版本显示
+    pub expose_as_alternative: Option<bool>,
+
+    /// 父文件编号
+    pub parent_project_file_id: Option<u32>,
+
+    /// 替代文件编号
+    pub alternate_file_id: Option<u32>,
+
+    /// 是否为服务端整合包
+    pub is_server_pack: Option<bool>,
+
+    /// 对应的服务端整合包文件编号
+    pub server_pack_file_id: Option<u32>,
+
+    /// 是否为抢先体验内容
+    pub is_early_access_content: Option<bool>,
+
+    /// 抢先体验结束时间
+    pub early_access_end_date: Option<String>,
+
+    /// 文件指纹，用于[`FingerprintsApi`](crate::curseforge::api::FingerprintsApi)
+    pub file_fingerprint: u32,
+
+    /// 文件内顶层目录和文件的指纹
+    pub modules: Vec<FileModule>,
+
     #[serde(flatten)]
     dynamic_fields: HashMap<String, Value>,
 }
 
 impl File {
+    /// 可以直接下载的地址，作者不允许第三方分发或[`download_url`](Self::download_url)
+    /// 为空字符串时返回`None`
+    pub fn direct_download_url(&self) -> Option<&str> {
+        self.download_url.as_deref().filter(|url| !url.is_empty())
+    }
+
+    /// 判断文件能否自动下载；不能时返回需要引导用户打开的文件页面
+    ///
+    /// `mod_website_url`为文件所属模组的网站地址，见[`Mod::website_url`](super::Mod::website_url)。
+    pub fn download(&self, mod_website_url: &str) -> FileDownload {
+        match self.direct_download_url() {
+            Some(url) => FileDownload::Automatic {
+                url: url.to_string(),
+            },
+            None => FileDownload::Manual {
+                website_url: format!(
+                    "{}/files/{}",
+                    mod_website_url.trim_end_matches('/'),
+                    self.id
+                ),
+            },
+        }
+    }
+
     pub fn md5(&self) -> Option<String> {
         let hash = self.hashes.iter().find(|hash| hash.algo == HashAlgo::Md5);
-        match hash {
-            Some(hash) => Some(hash.value.clone()),
-            None => None,
-        }
+        hash.map(|hash| hash.value.clone())
     }
 
     pub fn sha1(&self) -> Option<String> {
         let hash = self.hashes.iter().find(|hash| hash.algo == HashAlgo::Sha1);
-        match hash {
-            Some(hash) => Some(hash.value.clone()),
-            None => None,
-        }
+        hash.map(|hash| hash.value.clone())
     }
 }
 
-#[allow(dead_code)]
+/// 文件的获取方式
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileDownload {
+    /// 可以直接下载
+    Automatic { url: String },
+
+    /// 作者不允许第三方分发，需要用户在浏览器中打开文件页面手动下载
+    Manual { website_url: String },
+}
+
+/// 模组在某个游戏版本和加载器下的最新文件
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FileIndex {
-    game_version: String,
-    file_id: u32,
-    filename: String,
-    release_type: FileReleaseType,
-    game_version_type: Option<u32>,
-    mod_loader: ModLoaderType,
+    /// 游戏版本，如`1.20.1`
+    pub game_version: String,
+
+    /// 文件编号
+    pub file_id: u32,
+
+    /// 文件名
+    pub filename: String,
+
+    /// 发布类型
+    pub release_type: FileReleaseType,
+
+    /// 游戏版本类型编号
+    pub game_version_type_id: Option<u32>,
+
+    /// 模组加载器
+    pub mod_loader: Option<ModLoaderType>,
 }
 
 #[derive(Debug, serde_repr::Serialize_repr, serde_repr::Deserialize_repr, PartialEq)]
@@ -101,11 +192,45 @@ pub enum FileStatus {
     PostProcessing = 23,
 }
 
+/// 可排序的游戏版本
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SortableGameVersion {
+    /// 名称，如`1.19`或`Forge`
+    pub game_version_name: String,
+
+    /// 补零后的版本号，可直接按字符串排序
+    pub game_version_padded: String,
+
+    /// 版本号，加载器等非版本项为空
+    pub game_version: String,
+
+    /// 发布时间
+    pub game_version_release_date: String,
+
+    /// 游戏版本类型编号
+    pub game_version_type_id: Option<u32>,
+}
+
+/// 文件内的顶层目录或文件
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileModule {
+    /// 名称
+    pub name: String,
+
+    /// 指纹
+    pub fingerprint: u32,
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FileHash {
-    value: String,
-    algo: HashAlgo,
+    /// 哈希值
+    pub value: String,
+
+    /// 算法
+    pub algo: HashAlgo,
 }
 
 #[derive(Debug, serde_repr::Serialize_repr, serde_repr::Deserialize_repr, PartialEq)]
@@ -118,8 +243,11 @@ pub enum HashAlgo {
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FileDependency {
-    mod_id: u32,
-    relation_type: FileRelationType,
+    /// 依赖的模组编号
+    pub mod_id: u32,
+
+    /// 依赖关系
+    pub relation_type: FileRelationType,
 }
 
 #[derive(Debug, serde_repr::Serialize_repr, serde_repr::Deserialize_repr, PartialEq)]
@@ -241,6 +369,24 @@ mod tests {
         assert_eq!(file.release_type, FileReleaseType::Release);
         assert_eq!(file.file_status, FileStatus::Approved);
         assert_eq!(file.file_length, 359351);
+        assert!(file.is_available);
+        assert_eq!(file.sortable_game_versions[1].game_version, "1.19");
+        assert_eq!(
+            file.sortable_game_versions[1].game_version_type_id,
+            Some(73407)
+        );
+        assert_eq!(file.dependencies[0].mod_id, 531761);
+        assert_eq!(
+            file.dependencies[0].relation_type,
+            FileRelationType::RequiredDependency
+        );
+        assert_eq!(file.alternate_file_id, Some(0));
+        assert_eq!(file.is_server_pack, Some(false));
+        assert_eq!(file.server_pack_file_id, None);
+        assert_eq!(file.file_fingerprint, 2580803773);
+        assert_eq!(file.modules.len(), 8);
+        assert_eq!(file.modules[0].name, "META-INF");
+        assert_eq!(file.modules[0].fingerprint, 2731717902);
 
         if let Some(hash) = file.sha1() {
             assert_eq!(hash, "679b3c87f9145400ffb7fa81b2f918443cba72df");
@@ -248,5 +394,51 @@ mod tests {
         if let Some(hash) = file.md5() {
             assert_eq!(hash, "591352d7c666d2607ad4c473bb48ad10");
         }
+
+        let website_url = "https://www.curseforge.com/minecraft/mc-mods/waystones";
+        assert_eq!(
+            file.download(website_url),
+            FileDownload::Automatic {
+                url: String::from(
+                    "https://edge.forgecdn.net/files/3835/119/waystones-forge-1.19-11.0.0.jar"
+                )
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_restricted_file() {
+        let json = r#"{
+    "id": 4712479,
+    "gameId": 432,
+    "modId": 238222,
+    "isAvailable": true,
+    "displayName": "restricted.jar",
+    "fileName": "restricted.jar",
+    "releaseType": 1,
+    "fileStatus": 4,
+    "hashes": [],
+    "fileDate": "2023-08-20T10:00:00Z",
+    "fileLength": 1024,
+    "downloadCount": 0,
+    "downloadUrl": null,
+    "gameVersions": ["1.20.1"],
+    "sortableGameVersions": [],
+    "dependencies": [],
+    "isServerPack": true,
+    "fileFingerprint": 1,
+    "modules": []
+}"#;
+        let file: File = serde_json::from_str(json).unwrap();
+        assert_eq!(file.direct_download_url(), None);
+        assert_eq!(file.is_server_pack, Some(true));
+        assert_eq!(
+            file.download("https://www.curseforge.com/minecraft/mc-mods/jei/"),
+            FileDownload::Manual {
+                website_url: String::from(
+                    "https://www.curseforge.com/minecraft/mc-mods/jei/files/4712479"
+                )
+            }
+        );
     }
 }
@@ -0,0 +1,149 @@
+use super::File;
+
+/// 指纹精确匹配的结果
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FingerprintsMatchesResult {
+    /// 服务器的指纹缓存是否已建立
+    #[serde(default)]
+    pub is_cache_built: bool,
+
+    /// 精确匹配的文件
+    #[serde(default)]
+    pub exact_matches: Vec<FingerprintMatch>,
+
+    /// 精确匹配的指纹
+    #[serde(default)]
+    pub exact_fingerprints: Vec<u32>,
+
+    /// 部分匹配的文件
+    #[serde(default)]
+    pub partial_matches: Vec<FingerprintMatch>,
+
+    /// 已安装的指纹
+    #[serde(default)]
+    pub installed_fingerprints: Vec<u32>,
+
+    /// 没有匹配的指纹
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub unmatched_fingerprints: Vec<u32>,
+}
+
+/// 匹配到的文件
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FingerprintMatch {
+    /// 模组编号
+    pub id: u32,
+
+    /// 匹配的文件
+    pub file: File,
+
+    /// 模组的最新文件
+    #[serde(default)]
+    pub latest_files: Vec<File>,
+}
+
+/// 指纹模糊匹配的结果
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FingerprintFuzzyMatchResult {
+    #[serde(default)]
+    pub fuzzy_matches: Vec<FingerprintFuzzyMatch>,
+}
+
+/// 模糊匹配到的文件
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FingerprintFuzzyMatch {
+    /// 模组编号
+    pub id: u32,
+
+    /// 匹配的文件
+    pub file: File,
+
+    /// 模组的最新文件
+    #[serde(default)]
+    pub latest_files: Vec<File>,
+
+    /// 匹配的指纹
+    #[serde(default)]
+    pub fingerprints: Vec<u32>,
+}
+
+/// 用于模糊匹配的文件夹指纹
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FolderFingerprint {
+    /// 文件夹名称
+    pub foldername: String,
+
+    /// 文件夹内文件的指纹
+    pub fingerprints: Vec<u32>,
+}
+
+impl FolderFingerprint {
+    pub fn new(foldername: &str, fingerprints: Vec<u32>) -> Self {
+        Self {
+            foldername: foldername.to_string(),
+            fingerprints,
+        }
+    }
+}
+
+fn null_as_empty<'de, D>(deserializer: D) -> Result<Vec<u32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value: Option<Vec<u32>> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(value.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fingerprints_matches() {
+        let json = r#"{
+    "isCacheBuilt": true,
+    "exactMatches": [
+        {
+            "id": 238222,
+            "file": {
+                "id": 4712479,
+                "gameId": 432,
+                "modId": 238222,
+                "isAvailable": true,
+                "displayName": "jei-1.20.1-forge-15.2.0.27.jar",
+                "fileName": "jei-1.20.1-forge-15.2.0.27.jar",
+                "releaseType": 1,
+                "fileStatus": 4,
+                "hashes": [],
+                "fileDate": "2023-08-20T10:00:00Z",
+                "fileLength": 1024,
+                "downloadCount": 0,
+                "downloadUrl": null,
+                "gameVersions": ["1.20.1", "Forge"],
+                "sortableGameVersions": [],
+                "dependencies": [],
+                "fileFingerprint": 1396938946,
+                "modules": []
+            },
+            "latestFiles": []
+        }
+    ],
+    "exactFingerprints": [1396938946],
+    "partialMatches": [],
+    "partialMatchFingerprints": {},
+    "installedFingerprints": [1396938946, 3621685521],
+    "unmatchedFingerprints": null
+}"#;
+        let result: FingerprintsMatchesResult = serde_json::from_str(json).unwrap();
+        assert!(result.is_cache_built);
+        assert_eq!(result.exact_matches[0].id, 238222);
+        assert_eq!(result.exact_matches[0].file.id, 4712479);
+        assert_eq!(result.exact_fingerprints, [1396938946]);
+        assert_eq!(result.installed_fingerprints.len(), 2);
+        assert!(result.unmatched_fingerprints.is_empty());
+    }
+}
@@ -0,0 +1,304 @@
+use super::mod_loader::ModLoaderType;
+use super::versions::GameVersionTypeStatus;
+
+/// Minecraft版本
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MinecraftGameVersion {
+    /// 编号
+    pub id: u32,
+
+    /// 游戏版本编号
+    pub game_version_id: u32,
+
+    /// 版本号，如`1.20.1`
+    pub version_string: String,
+
+    /// 客户端jar下载地址
+    pub jar_download_url: String,
+
+    /// 版本JSON下载地址
+    pub json_download_url: String,
+
+    /// 是否已审核
+    pub approved: bool,
+
+    /// 修改时间
+    pub date_modified: String,
+
+    /// 游戏版本类型编号
+    pub game_version_type_id: u32,
+
+    /// 版本状态
+    pub game_version_status: GameVersionStatus,
+
+    /// 版本类型状态
+    pub game_version_type_status: GameVersionTypeStatus,
+}
+
+/// 模组加载器列表中的一项
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MinecraftModLoaderIndex {
+    /// 名称，如`forge-47.2.0`
+    pub name: String,
+
+    /// 对应的Minecraft版本
+    pub game_version: String,
+
+    /// 是否为该Minecraft版本的最新版
+    pub latest: bool,
+
+    /// 是否为该Minecraft版本的推荐版
+    pub recommended: bool,
+
+    /// 修改时间
+    pub date_modified: String,
+
+    /// 加载器类型
+    #[serde(rename = "type")]
+    pub mod_loader_type: ModLoaderType,
+}
+
+/// 模组加载器详情
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MinecraftModLoaderVersion {
+    /// 编号
+    pub id: u32,
+
+    /// 游戏版本编号
+    pub game_version_id: u32,
+
+    /// Minecraft版本编号
+    pub minecraft_game_version_id: u32,
+
+    /// 加载器版本号，如`47.2.0`
+    #[serde(default)]
+    pub forge_version: Option<String>,
+
+    /// 名称，如`forge-47.2.0`
+    pub name: String,
+
+    /// 加载器类型
+    #[serde(rename = "type")]
+    pub mod_loader_type: ModLoaderType,
+
+    /// 下载地址，Fabric等不提供安装器的加载器为空
+    #[serde(default)]
+    pub download_url: Option<String>,
+
+    /// 文件名
+    #[serde(default)]
+    pub filename: Option<String>,
+
+    /// 安装方式
+    pub install_method: ModLoaderInstallMethod,
+
+    /// 是否为该Minecraft版本的最新版
+    pub latest: bool,
+
+    /// 是否为该Minecraft版本的推荐版
+    pub recommended: bool,
+
+    /// 是否已审核
+    pub approved: bool,
+
+    /// 修改时间
+    pub date_modified: String,
+
+    /// Maven坐标
+    #[serde(default)]
+    pub maven_version_string: Option<String>,
+
+    /// 版本JSON
+    pub version_json: String,
+
+    /// 依赖库的安装位置
+    #[serde(default)]
+    pub libraries_install_location: Option<String>,
+
+    /// 对应的Minecraft版本
+    pub minecraft_version: String,
+
+    /// 额外文件的JSON
+    #[serde(default)]
+    pub additional_files_json: Option<String>,
+
+    /// 安装配置JSON，只有Forge安装器v2和NeoForge提供
+    #[serde(default)]
+    pub install_profile_json: Option<String>,
+}
+
+/// Minecraft版本的状态
+#[derive(
+    Debug, Clone, Copy, serde_repr::Serialize_repr, serde_repr::Deserialize_repr, PartialEq,
+)]
+#[repr(u8)]
+pub enum GameVersionStatus {
+    Approved = 1,
+    Deleted = 2,
+    New = 3,
+}
+
+/// 模组加载器的安装方式
+///
+/// API以后可能增加新的安装方式，无法识别的值解析为[`Unknown`](Self::Unknown)。
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(from = "i32", into = "i32")]
+pub enum ModLoaderInstallMethod {
+    ForgeInstaller,
+    ForgeJarInstall,
+    ForgeInstallerV2,
+    /// 未知的安装方式，保留原始值
+    Unknown(i32),
+}
+
+impl From<i32> for ModLoaderInstallMethod {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::ForgeInstaller,
+            2 => Self::ForgeJarInstall,
+            3 => Self::ForgeInstallerV2,
+            value => Self::Unknown(value),
+        }
+    }
+}
+
+impl From<ModLoaderInstallMethod> for i32 {
+    fn from(value: ModLoaderInstallMethod) -> Self {
+        match value {
+            ModLoaderInstallMethod::ForgeInstaller => 1,
+            ModLoaderInstallMethod::ForgeJarInstall => 2,
+            ModLoaderInstallMethod::ForgeInstallerV2 => 3,
+            ModLoaderInstallMethod::Unknown(value) => value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_minecraft_game_version() {
+        let json = r#"{
+    "id": 1209,
+    "gameVersionId": 9990,
+    "versionString": "1.20.1",
+    "jarDownloadUrl": "https://piston-data.mojang.com/v1/objects/client.jar",
+    "jsonDownloadUrl": "https://piston-meta.mojang.com/v1/packages/1.20.1.json",
+    "approved": true,
+    "dateModified": "2023-06-12T14:26:38.477Z",
+    "gameVersionTypeId": 75125,
+    "gameVersionStatus": 1,
+    "gameVersionTypeStatus": 1
+}"#;
+        let version: MinecraftGameVersion = serde_json::from_str(json).unwrap();
+        assert_eq!(version.version_string, "1.20.1");
+        assert_eq!(version.game_version_type_id, 75125);
+        assert_eq!(version.game_version_status, GameVersionStatus::Approved);
+    }
+
+    #[test]
+    fn test_parse_mod_loader_version() {
+        let json = r#"{
+    "id": 7657,
+    "gameVersionId": 9990,
+    "minecraftGameVersionId": 1209,
+    "forgeVersion": "47.2.0",
+    "name": "forge-47.2.0",
+    "type": 1,
+    "downloadUrl": "https://maven.minecraftforge.net/net/minecraftforge/forge/1.20.1-47.2.0/forge-1.20.1-47.2.0-installer.jar",
+    "filename": "forge-1.20.1-47.2.0-installer.jar",
+    "installMethod": 3,
+    "latest": false,
+    "recommended": true,
+    "approved": true,
+    "dateModified": "2023-09-12T22:02:26.837Z",
+    "mavenVersionString": "net.minecraftforge:forge:1.20.1-47.2.0",
+    "versionJson": "{\"id\": \"1.20.1-forge-47.2.0\"}",
+    "librariesInstallLocation": "",
+    "minecraftVersion": "1.20.1",
+    "additionalFilesJson": null,
+    "modLoaderGameVersionId": 0,
+    "installProfileJson": "{}"
+}"#;
+        let loader: MinecraftModLoaderVersion = serde_json::from_str(json).unwrap();
+        assert_eq!(loader.mod_loader_type, ModLoaderType::Forge);
+        assert_eq!(
+            loader.install_method,
+            ModLoaderInstallMethod::ForgeInstallerV2
+        );
+        assert!(loader.version_json.contains("1.20.1-forge-47.2.0"));
+        assert_eq!(loader.forge_version.as_deref(), Some("47.2.0"));
+
+        let json = r#"{"name": "neoforge-21.1.77", "gameVersion": "1.21.1", "latest": true,
+            "recommended": false, "dateModified": "2024-11-10T00:00:00Z", "type": 6}"#;
+        let index: MinecraftModLoaderIndex = serde_json::from_str(json).unwrap();
+        assert_eq!(index.mod_loader_type, ModLoaderType::NeoForge);
+    }
+
+    #[test]
+    fn test_parse_fabric_loader_version() {
+        let json = r#"{
+    "id": 12156,
+    "gameVersionId": 9990,
+    "minecraftGameVersionId": 1209,
+    "forgeVersion": null,
+    "name": "fabric-0.15.11-1.20.1",
+    "type": 4,
+    "downloadUrl": null,
+    "filename": null,
+    "installMethod": 4,
+    "latest": true,
+    "recommended": false,
+    "approved": true,
+    "dateModified": "2024-05-10T18:20:54.17Z",
+    "mavenVersionString": "net.fabricmc:fabric-loader:0.15.11",
+    "versionJson": "{\"id\": \"fabric-loader-0.15.11-1.20.1\"}",
+    "librariesInstallLocation": null,
+    "minecraftVersion": "1.20.1",
+    "modLoaderGameVersionId": 0
+}"#;
+        let loader: MinecraftModLoaderVersion = serde_json::from_str(json).unwrap();
+        assert_eq!(loader.mod_loader_type, ModLoaderType::Fabric);
+        assert_eq!(loader.install_method, ModLoaderInstallMethod::Unknown(4));
+        assert_eq!(loader.forge_version, None);
+        assert_eq!(loader.download_url, None);
+        assert_eq!(loader.install_profile_json, None);
+        assert_eq!(serde_json::to_value(loader.install_method).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_parse_neoforge_loader_version() {
+        let json = r#"{
+    "id": 13587,
+    "gameVersionId": 11779,
+    "minecraftGameVersionId": 1296,
+    "forgeVersion": "21.1.77",
+    "name": "neoforge-21.1.77",
+    "type": 6,
+    "downloadUrl": "https://maven.neoforged.net/releases/net/neoforged/neoforge/21.1.77/neoforge-21.1.77-installer.jar",
+    "filename": "neoforge-21.1.77-installer.jar",
+    "installMethod": 3,
+    "latest": true,
+    "recommended": false,
+    "approved": true,
+    "dateModified": "2024-11-10T00:12:41.91Z",
+    "mavenVersionString": "net.neoforged:neoforge:21.1.77",
+    "versionJson": "{\"id\": \"neoforge-21.1.77\"}",
+    "librariesInstallLocation": "",
+    "minecraftVersion": "1.21.1",
+    "installProfileJson": "{\"profile\": \"NeoForge\"}"
+}"#;
+        let loader: MinecraftModLoaderVersion = serde_json::from_str(json).unwrap();
+        assert_eq!(loader.mod_loader_type, ModLoaderType::NeoForge);
+        assert_eq!(
+            loader.install_method,
+            ModLoaderInstallMethod::ForgeInstallerV2
+        );
+        assert_eq!(loader.additional_files_json, None);
+        assert!(loader.install_profile_json.unwrap().contains("NeoForge"));
+    }
+}
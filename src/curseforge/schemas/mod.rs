@@ -1,6 +1,8 @@
 mod categories;
 mod files;
+mod fingerprints;
 mod games;
+mod minecraft;
 mod mod_loader;
 mod mods;
 mod pagination;
@@ -9,9 +11,27 @@ mod response;
 mod versions;
 
 pub use categories::Category;
-pub use files::File;
+pub use files::{
+    File, FileDependency, FileDownload, FileHash, FileIndex, FileModule, FileRelationType,
+    FileReleaseType, FileStatus, HashAlgo, SortableGameVersion,
+};
+pub use fingerprints::{
+    FingerprintFuzzyMatch, FingerprintFuzzyMatchResult, FingerprintMatch,
+    FingerprintsMatchesResult, FolderFingerprint,
+};
 pub use games::Game;
-pub use mods::Mod;
-pub use parameters::{GetModFilesParameters, SearchModsParameters};
-pub use response::{DataResponse, ListResponse, PaginationResponse};
-pub use versions::{GameVersionType, GameVersionsByType};
+pub use minecraft::{
+    GameVersionStatus, MinecraftGameVersion, MinecraftModLoaderIndex, MinecraftModLoaderVersion,
+    ModLoaderInstallMethod,
+};
+pub use mod_loader::ModLoaderType;
+pub use mods::{FeaturedModsResponse, Mod, ModAsset, ModAuthor, ModLinks, ModStatus};
+pub(crate) use parameters::{
+    GetFeaturedModsBody, GetFilesBody, GetFingerprintFuzzyMatchesBody, GetFingerprintMatchesBody,
+    GetModsBody,
+};
+pub use parameters::{GetModFilesParameters, SearchModsParameters, SortField, SortOrder};
+pub use response::{BatchResponse, DataResponse, ListResponse, PaginationResponse};
+pub use versions::{
+    GameVersion, GameVersionType, GameVersionTypeStatus, GameVersionsByType, GameVersionsByTypeV2,
+};
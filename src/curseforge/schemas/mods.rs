@@ -3,107 +3,132 @@ use std::collections::HashMap;
 use serde_json::Value;
 
 use super::Category;
+use super::files::{File, FileIndex};
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Mod {
     /// 编号
-    id: u32,
+    pub id: u32,
 
     /// 游戏编号
-    game_id: u32,
+    pub game_id: u32,
 
     /// 名称
-    name: String,
+    pub name: String,
 
     /// Slug
-    slug: String,
+    pub slug: String,
 
     /// 链接
-    // links: ModLinks,
+    pub links: ModLinks,
 
     /// 概要
-    summary: String,
+    pub summary: String,
 
     /// 状态
-    status: ModStatus,
+    pub status: ModStatus,
 
     /// 下载量
-    download_count: u64,
+    pub download_count: u64,
 
-    // is_featured
-    primary_category_id: u32,
+    /// 是否为推荐模组
+    pub is_featured: bool,
+
+    /// 主要种类编号
+    pub primary_category_id: u32,
 
     /// 种类/分类
-    categories: Vec<Category>,
+    pub categories: Vec<Category>,
 
     /// 分类编号
-    class_id: Option<u32>,
+    pub class_id: Option<u32>,
 
     /// 作者
-    authors: Vec<ModAuthor>,
+    pub authors: Vec<ModAuthor>,
 
     /// Logo
-    logo: ModAsset,
+    pub logo: ModAsset,
 
     /// 截图
-    // screenshots: Vec<ModAsset>,
+    pub screenshots: Vec<ModAsset>,
 
     /// 主文件
-    main_file_id: u32,
+    pub main_file_id: u32,
 
     /// 最新文件
-    // latest_files: Vec<File>,
+    pub latest_files: Vec<File>,
 
     /// 最新文件索引
-    // latest_files_indexes: Vec<FileIndex>,
+    pub latest_files_indexes: Vec<FileIndex>,
 
     /// 最新预览版文件索引
-    // latest_early_access_files_indexes: Vec<FileIndex>,
+    pub latest_early_access_files_indexes: Vec<FileIndex>,
 
     /// 创建时间
-    date_created: String,
+    pub date_created: String,
 
     /// 修改时间
-    date_modified: String,
+    pub date_modified: String,
 
     /// 发布时间
-    date_released: String,
+    pub date_released: String,
 
-    /// 是否允许模组分发
-    // allow_mod_distribution: bool,
+    /// 是否允许第三方分发，为`false`时文件没有下载地址
+    pub allow_mod_distribution: Option<bool>,
 
     /// 游戏流行度排名
-    // game_popularity_rank
+    pub game_popularity_rank: u32,
 
-    /// 是否允许搜索
-    // is_available: bool,
+    /// 是否可以搜索到
+    pub is_available: bool,
 
-    /// 评论数量？
-    // thumbs_up_count: u32,
+    /// 点赞数量
+    pub thumbs_up_count: u32,
 
     /// 评分
-    // rating: Option<f32>,
+    pub rating: Option<f32>,
 
     /// 未知字段
     #[serde(flatten)]
     dynamic_fields: HashMap<String, Value>,
 }
 
+impl Mod {
+    /// CurseForge上的模组页面
+    pub fn website_url(&self) -> &str {
+        &self.links.website_url
+    }
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ModLinks {
     /// 网站
-    website_url: String,
+    pub website_url: String,
 
     /// Wiki
-    wiki_url: String,
+    pub wiki_url: Option<String>,
 
     /// 问题汇报
-    issues_url: String,
+    pub issues_url: Option<String>,
 
     /// 源码
-    source_url: String,
+    pub source_url: Option<String>,
+}
+
+/// 推荐、热门和最近更新的模组
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeaturedModsResponse {
+    /// 推荐
+    pub featured: Vec<Mod>,
+
+    /// 热门
+    pub popular: Vec<Mod>,
+
+    /// 最近更新
+    pub recently_updated: Vec<Mod>,
 }
 
 #[derive(Debug, serde_repr::Serialize_repr, serde_repr::Deserialize_repr, PartialEq)]
@@ -125,40 +150,41 @@ pub enum ModStatus {
 #[serde(rename_all = "camelCase")]
 pub struct ModAuthor {
     /// 编号
-    id: u32,
+    pub id: u32,
 
     /// 名称
-    name: String,
+    pub name: String,
 
     /// URL
-    url: String,
+    pub url: String,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ModAsset {
     /// 编号
-    id: u32,
+    pub id: u32,
 
     /// 模组编号
-    mod_id: u32,
+    pub mod_id: u32,
 
     /// 标题
-    title: String,
+    pub title: String,
 
     /// 描述
-    description: String,
+    pub description: String,
 
     /// 缩略图URL
-    thumbnail_url: String,
+    pub thumbnail_url: String,
 
     /// 下载地址
-    url: String,
+    pub url: String,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::curseforge::schemas::ModLoaderType;
 
     #[test]
     fn test_parse_jei() {
@@ -1759,6 +1785,10 @@ mod tests {
         assert_eq!(jei.game_id, 432);
         assert_eq!(jei.name, "Just Enough Items (JEI)");
         assert_eq!(jei.slug, "jei");
+        assert_eq!(
+            jei.website_url(),
+            "https://www.curseforge.com/minecraft/mc-mods/jei"
+        );
         assert_eq!(jei.primary_category_id, 423);
         assert!(jei.class_id.is_some());
         assert_eq!(jei.class_id.unwrap(), 6);
@@ -1767,14 +1797,20 @@ mod tests {
         assert_eq!(logo.id, 29069);
         assert_eq!(logo.mod_id, 238222);
 
-        if let Some(allow_mod_distribution) = jei.dynamic_fields.get("allowModDistribution") {
-            assert!(allow_mod_distribution.is_boolean());
-            assert_eq!(allow_mod_distribution.as_bool().unwrap(), true);
-        }
+        assert_eq!(jei.allow_mod_distribution, Some(true));
+        assert!(jei.is_available);
+        assert!(!jei.is_featured);
+        assert_eq!(jei.game_popularity_rank, 1);
+        assert_eq!(jei.thumbs_up_count, 1);
+        assert!(jei.rating.is_none());
+        assert_eq!(jei.screenshots[0].id, 31420);
+        assert_eq!(jei.latest_files[0].id, 3040523);
+        assert!(jei.latest_early_access_files_indexes.is_empty());
 
-        if let Some(is_available) = jei.dynamic_fields.get("isAvailable") {
-            assert!(is_available.is_boolean());
-            assert_eq!(is_available.as_bool().unwrap(), true);
-        }
+        let index = &jei.latest_files_indexes[0];
+        assert_eq!(index.file_id, 6075247);
+        assert_eq!(index.game_version_type_id, Some(75125));
+        assert_eq!(index.mod_loader, Some(ModLoaderType::Forge));
+        assert_eq!(jei.dynamic_fields["featuredProjectTag"], 0);
     }
 }
@@ -1,4 +1,5 @@
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Pagination {
     pub index: i32,
     pub page_size: i32,
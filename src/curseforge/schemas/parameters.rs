@@ -1,24 +1,210 @@
+use super::FolderFingerprint;
 use super::mod_loader::ModLoaderType;
 
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
+/// 获取模组文件参数
+#[derive(Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetModFilesParameters {
-    //
+    /// 游戏版本
+    pub game_version: Option<String>,
+
+    /// 模组加载器类型
+    pub mod_loader_type: Option<ModLoaderType>,
+
+    /// 游戏版本类型编号
+    pub game_version_type_id: Option<u32>,
+
+    /// 起始位置
+    pub index: Option<u32>,
+
+    /// 每页数量
+    pub page_size: Option<u32>,
+}
+
+impl GetModFilesParameters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn game_version(mut self, game_version: &str) -> Self {
+        self.game_version = Some(game_version.to_string());
+        self
+    }
+
+    pub fn mod_loader_type(mut self, mod_loader_type: ModLoaderType) -> Self {
+        self.mod_loader_type = Some(mod_loader_type);
+        self
+    }
+
+    pub fn game_version_type_id(mut self, game_version_type_id: u32) -> Self {
+        self.game_version_type_id = Some(game_version_type_id);
+        self
+    }
+
+    pub fn index(mut self, index: u32) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
 }
 
+/// 搜索模组参数
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SearchModsParameters {
-    game_id: u32,
-    class_id: Option<u32>,
-    category_id: Option<u32>,
-    game_version: Option<String>,
-    search_filter: Option<String>,
-    sort_field: Option<SortField>,
-    sort_order: Option<SortOrder>,
-    mod_loader_type: Option<ModLoaderType>,
-    game_version_type_id: Option<u32>,
-    slug: Option<String>,
-    index: Option<u32>,
-    page_size: Option<u32>,
+    /// 游戏编号
+    pub game_id: u32,
+
+    /// 分类编号
+    pub class_id: Option<u32>,
+
+    /// 种类编号
+    pub category_id: Option<u32>,
+
+    /// 游戏版本
+    pub game_version: Option<String>,
+
+    /// 搜索关键字
+    pub search_filter: Option<String>,
+
+    /// 排序字段
+    pub sort_field: Option<SortField>,
+
+    /// 排序方式
+    pub sort_order: Option<SortOrder>,
+
+    /// 模组加载器类型
+    pub mod_loader_type: Option<ModLoaderType>,
+
+    /// 游戏版本类型编号
+    pub game_version_type_id: Option<u32>,
+
+    /// Slug
+    pub slug: Option<String>,
+
+    /// 起始位置
+    pub index: Option<u32>,
+
+    /// 每页数量
+    pub page_size: Option<u32>,
+}
+
+impl SearchModsParameters {
+    pub fn new(game_id: u32) -> Self {
+        Self {
+            game_id,
+            class_id: None,
+            category_id: None,
+            game_version: None,
+            search_filter: None,
+            sort_field: None,
+            sort_order: None,
+            mod_loader_type: None,
+            game_version_type_id: None,
+            slug: None,
+            index: None,
+            page_size: None,
+        }
+    }
+
+    pub fn class_id(mut self, class_id: u32) -> Self {
+        self.class_id = Some(class_id);
+        self
+    }
+
+    pub fn category_id(mut self, category_id: u32) -> Self {
+        self.category_id = Some(category_id);
+        self
+    }
+
+    pub fn game_version(mut self, game_version: &str) -> Self {
+        self.game_version = Some(game_version.to_string());
+        self
+    }
+
+    pub fn search_filter(mut self, search_filter: &str) -> Self {
+        self.search_filter = Some(search_filter.to_string());
+        self
+    }
+
+    pub fn sort_field(mut self, sort_field: SortField) -> Self {
+        self.sort_field = Some(sort_field);
+        self
+    }
+
+    pub fn sort_order(mut self, sort_order: SortOrder) -> Self {
+        self.sort_order = Some(sort_order);
+        self
+    }
+
+    pub fn mod_loader_type(mut self, mod_loader_type: ModLoaderType) -> Self {
+        self.mod_loader_type = Some(mod_loader_type);
+        self
+    }
+
+    pub fn game_version_type_id(mut self, game_version_type_id: u32) -> Self {
+        self.game_version_type_id = Some(game_version_type_id);
+        self
+    }
+
+    pub fn slug(mut self, slug: &str) -> Self {
+        self.slug = Some(slug.to_string());
+        self
+    }
+
+    pub fn index(mut self, index: u32) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+}
+
+/// `POST /v1/mods`的请求体
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GetModsBody<'a> {
+    pub(crate) mod_ids: &'a [u32],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) filter_pc_only: Option<bool>,
+}
+
+/// `POST /v1/mods/files`的请求体
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GetFilesBody<'a> {
+    pub(crate) file_ids: &'a [u32],
+}
+
+/// `POST /v1/mods/featured`的请求体
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GetFeaturedModsBody<'a> {
+    pub(crate) game_id: u32,
+    pub(crate) excluded_mod_ids: &'a [u32],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) game_version_type_id: Option<u32>,
+}
+
+/// `POST /v1/fingerprints`的请求体
+#[derive(Debug, serde::Serialize)]
+pub(crate) struct GetFingerprintMatchesBody<'a> {
+    pub(crate) fingerprints: &'a [u32],
+}
+
+/// `POST /v1/fingerprints/fuzzy`的请求体
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GetFingerprintFuzzyMatchesBody<'a> {
+    pub(crate) game_id: u32,
+    pub(crate) fingerprints: &'a [FolderFingerprint],
 }
 
 #[derive(Debug, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
@@ -45,3 +231,71 @@ pub enum SortOrder {
     #[allow(non_camel_case_types)]
     desc,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode<Q: serde::Serialize>(params: &Q) -> String {
+        let request = reqwest::Client::new()
+            .get("https://api.curseforge.com/v1/mods/search")
+            .query(params)
+            .build()
+            .unwrap();
+        request.url().query().unwrap_or_default().to_string()
+    }
+
+    #[test]
+    fn test_encode_search_mods_parameters() {
+        let params = SearchModsParameters::new(432)
+            .class_id(6)
+            .category_id(423)
+            .game_version("1.20.1")
+            .search_filter("just enough items")
+            .sort_field(SortField::Popularity)
+            .sort_order(SortOrder::desc)
+            .mod_loader_type(ModLoaderType::Forge)
+            .game_version_type_id(75125)
+            .slug("jei")
+            .index(50)
+            .page_size(25);
+
+        assert_eq!(
+            encode(&params),
+            "gameId=432&classId=6&categoryId=423&gameVersion=1.20.1\
+             &searchFilter=just+enough+items&sortField=2&sortOrder=desc\
+             &modLoaderType=1&gameVersionTypeId=75125&slug=jei&index=50&pageSize=25"
+        );
+    }
+
+    #[test]
+    fn test_encode_search_mods_parameters_skips_none() {
+        let params = SearchModsParameters::new(432);
+        assert_eq!(encode(&params), "gameId=432");
+    }
+
+    #[test]
+    fn test_encode_get_mod_files_parameters() {
+        let params = GetModFilesParameters::new()
+            .game_version("1.20.1")
+            .mod_loader_type(ModLoaderType::NeoForge)
+            .game_version_type_id(75125)
+            .index(0)
+            .page_size(50);
+
+        assert_eq!(
+            encode(&params),
+            "gameVersion=1.20.1&modLoaderType=6&gameVersionTypeId=75125&index=0&pageSize=50"
+        );
+        assert_eq!(encode(&GetModFilesParameters::new()), "");
+    }
+
+    #[test]
+    fn test_body_skips_none() {
+        let body = GetModsBody {
+            mod_ids: &[1, 2],
+            filter_pc_only: None,
+        };
+        assert_eq!(serde_json::to_string(&body).unwrap(), r#"{"modIds":[1,2]}"#);
+    }
+}
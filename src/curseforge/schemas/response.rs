@@ -7,6 +7,15 @@ pub struct DataResponse<D> {
 
 pub type ListResponse<D> = DataResponse<Vec<D>>;
 
+/// 批量获取的结果
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct BatchResponse<D> {
+    pub data: D,
+
+    /// 服务器没有返回的编号
+    pub missing_ids: Vec<u32>,
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct PaginationResponse<D> {
     pub data: Vec<D>,
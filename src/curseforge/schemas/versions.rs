@@ -7,19 +7,102 @@ pub struct GameVersionsByType {
     pub versions: Vec<String>,
 }
 
+/// 按类型分组的版本（v2），包含版本的编号和别名
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct GameVersionsByTypeV2 {
+    /// 版本类型编号，即`gameVersionTypeId`
+    #[serde(rename = "type")]
+    pub version_type: i32,
+
+    pub versions: Vec<GameVersion>,
+}
+
+impl GameVersionsByTypeV2 {
+    /// 按名称查找版本，如`1.20.1`
+    pub fn version(&self, name: &str) -> Option<&GameVersion> {
+        self.versions.iter().find(|version| version.name == name)
+    }
+}
+
+/// 游戏版本
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct GameVersion {
+    /// 编号
+    pub id: u32,
+
+    /// 别名，如`1-20-1`
+    pub slug: String,
+
+    /// 名称，如`1.20.1`
+    pub name: String,
+}
+
+/// 游戏版本类型
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GameVersionType {
-    id: i32,
-    game_id: i32,
-    name: String,
-    slug: String,
-    is_syncable: bool,
-    status: GameVersionTypeStatus,
+    /// 编号
+    pub id: i32,
+
+    /// 游戏编号
+    pub game_id: i32,
+
+    /// 名称，如`Minecraft 1.20`
+    pub name: String,
+
+    /// 别名，如`minecraft-1-20`
+    pub slug: String,
+
+    /// 是否可同步
+    pub is_syncable: bool,
+
+    /// 状态
+    pub status: GameVersionTypeStatus,
 }
 
-#[derive(Debug, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[derive(
+    Debug, Clone, Copy, serde_repr::Serialize_repr, serde_repr::Deserialize_repr, PartialEq,
+)]
 #[repr(u8)]
 pub enum GameVersionTypeStatus {
     Normal = 1,
     Deleted = 2,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_versions_v2() {
+        let json = r#"[{
+    "type": 75125,
+    "versions": [
+        {"id": 9990, "slug": "1-20-1", "name": "1.20.1"},
+        {"id": 9366, "slug": "1-19-2", "name": "1.19.2"}
+    ]
+}]"#;
+        let versions: Vec<GameVersionsByTypeV2> = serde_json::from_str(json).unwrap();
+        assert_eq!(versions[0].version_type, 75125);
+        assert_eq!(versions[0].version("1.19.2").unwrap().id, 9366);
+        assert!(versions[0].version("1.12.2").is_none());
+    }
+
+    #[test]
+    fn test_parse_version_types() {
+        let json = r#"[{
+    "id": 75125,
+    "gameId": 432,
+    "name": "Minecraft 1.20",
+    "slug": "minecraft-1-20",
+    "isSyncable": true,
+    "status": 1
+}]"#;
+        let types: Vec<GameVersionType> = serde_json::from_str(json).unwrap();
+        assert_eq!(types[0].id, 75125);
+        assert_eq!(types[0].game_id, 432);
+        assert_eq!(types[0].slug, "minecraft-1-20");
+        assert!(types[0].is_syncable);
+        assert_eq!(types[0].status, GameVersionTypeStatus::Normal);
+    }
+}
@@ -1,4 +1 @@
-#[derive(Debug)]
-pub enum Error {
-    //
-}
+pub use crate::curseforge::error::{CurseForgeResult, Error};
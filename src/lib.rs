@@ -1,5 +1,21 @@
+#[cfg(not(any(feature = "blocking", feature = "async")))]
+compile_error!("at least one of the `blocking` and `async` features must be enabled");
+
 pub mod curseforge;
 pub mod error;
 
-pub use curseforge::client::{Client, ClientBuilder};
+#[cfg(feature = "async")]
+pub use curseforge::client::AsyncClient;
+#[cfg(feature = "blocking")]
+pub use curseforge::client::Client;
+#[cfg(feature = "async")]
+pub use curseforge::client::{AsyncReqwestTransport, AsyncTransport, TransportFuture};
+pub use curseforge::client::{
+    CacheOptions, CassetteTransport, ClientBuilder, Fault, FaultTransport, FixtureTransport,
+    Middleware, RetryPolicy,
+};
+#[cfg(feature = "blocking")]
+pub use curseforge::client::{ReqwestTransport, Transport};
+#[cfg(feature = "mock-server")]
+pub use curseforge::mock_server::{MockData, MockServer};
 pub use curseforge::schemas::{Category, File, Game, Mod};
@@ -1,3 +1,57 @@
-fn main() {
-    println!("Hello, world!");
+//! 本地CurseForge模拟服务器
+//!
+//! ```text
+//! curseforge-mock-server [--fixtures <DIR>] [--address <ADDR>]
+//! ```
+
+use std::process::ExitCode;
+
+use curseforge_api::{MockData, MockServer};
+
+const USAGE: &str = "usage: curseforge-mock-server [--fixtures <DIR>] [--address <ADDR>]";
+
+fn main() -> ExitCode {
+    let mut fixtures = String::from("fixtures");
+    let mut address = String::from("127.0.0.1:8080");
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = match arg.as_str() {
+            "--fixtures" => &mut fixtures,
+            "--address" => &mut address,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            _ => {
+                eprintln!("unknown argument `{}`\n{}", arg, USAGE);
+                return ExitCode::FAILURE;
+            }
+        };
+        match args.next() {
+            Some(arg) => *value = arg,
+            None => {
+                eprintln!("missing value for `{}`\n{}", arg, USAGE);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let data = match MockData::load(&fixtures) {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let server = match MockServer::bind(address.as_str(), data) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("cannot bind {}: {}", address, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("listening on {}", server.base_url());
+    server.join();
+    ExitCode::SUCCESS
 }